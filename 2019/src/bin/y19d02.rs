@@ -2,10 +2,10 @@ const YEAR: u16 = 2019;
 const DAY: u8 = 2;
 use aoc::{intcode::CPU, *};
 
-type ResultType = u64;
+type ResultType = i64;
 type DataType = intcode::CPU;
 
-fn run_program(mut cpu: CPU, noun: u64, verb: u64) -> Result<ResultType> {
+fn run_program(cpu: &mut CPU, noun: i64, verb: i64) -> Result<ResultType> {
     cpu.write(1, noun)?;
     cpu.write(2, verb)?;
     cpu.run()?;
@@ -17,13 +17,16 @@ fn parse(input: &str) -> Result<DataType> {
 }
 
 fn task1(cpu: &DataType) -> Result<ResultType> {
-    run_program(cpu.clone(), 12, 2)
+    run_program(&mut cpu.clone(), 12, 2)
 }
 
 fn task2(cpu: &DataType) -> Result<ResultType> {
+    let snapshot = cpu.snapshot();
+    let mut run = cpu.clone();
     for noun in 0..=99 {
         for verb in 0..=99 {
-            if run_program(cpu.clone(), noun, verb)? == 19690720 {
+            run.restore(&snapshot);
+            if run_program(&mut run, noun, verb)? == 19690720 {
                 return Ok(100 * noun + verb);
             }
         }
@@ -1,41 +1,15 @@
 const YEAR: u16 = 2019;
 const DAY: u8 = 3;
 
-use aoc::{utils::*, *};
+use aoc::{
+    utils::{Parser, *},
+    *,
+};
 use std::{
     collections::{HashMap, HashSet},
     str::FromStr,
 };
 
-#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
-struct Position(i64, i64);
-
-impl Position {
-    fn up(&self) -> Position {
-        Position(self.0, self.1 + 1)
-    }
-
-    fn down(&self) -> Position {
-        Position(self.0, self.1 - 1)
-    }
-
-    fn right(&self) -> Position {
-        Position(self.0 + 1, self.1)
-    }
-
-    fn left(&self) -> Position {
-        Position(self.0 - 1, self.1)
-    }
-
-    fn manhattan(&self) -> u64 {
-        self.0.unsigned_abs() + self.1.unsigned_abs()
-    }
-
-    fn is_origin(&self) -> bool {
-        self.0 == 0 && self.1 == 0
-    }
-}
-
 #[derive(Debug)]
 enum Segment {
     Up(usize),
@@ -76,7 +50,7 @@ impl Wire {
     fn positions(&self) -> PositionIterator<'_> {
         PositionIterator {
             wire: self,
-            next_position: Some(Position(0, 0)),
+            next_position: Some(Point::ORIGIN),
             segment: 0,
             segment_step: 0,
         }
@@ -92,22 +66,22 @@ impl Wire {
 
 struct PositionIterator<'a> {
     wire: &'a Wire,
-    next_position: Option<Position>,
+    next_position: Option<Point>,
     segment: usize,
     segment_step: usize,
 }
 
 impl<'a> Iterator for PositionIterator<'a> {
-    type Item = Position;
+    type Item = Point;
 
     fn next(&mut self) -> Option<Self::Item> {
         let current_position = self.next_position?;
 
         let (next, length) = match self.wire.0.get(self.segment) {
-            Some(Segment::Up(length)) => (Some(current_position.up()), *length),
-            Some(Segment::Down(length)) => (Some(current_position.down()), *length),
-            Some(Segment::Left(length)) => (Some(current_position.left()), *length),
-            Some(Segment::Right(length)) => (Some(current_position.right()), *length),
+            Some(Segment::Up(length)) => (Some(current_position + Direction::North), *length),
+            Some(Segment::Down(length)) => (Some(current_position + Direction::South), *length),
+            Some(Segment::Left(length)) => (Some(current_position + Direction::West), *length),
+            Some(Segment::Right(length)) => (Some(current_position + Direction::East), *length),
             None => (None, 0usize),
         };
 
@@ -128,7 +102,7 @@ struct PositionDistanceIterator<'a> {
 }
 
 impl<'a> Iterator for PositionDistanceIterator<'a> {
-    type Item = (Position, u64);
+    type Item = (Point, u64);
 
     fn next(&mut self) -> Option<Self::Item> {
         let current_distance = self.distance;
@@ -150,12 +124,12 @@ fn parse(input: &str) -> Result<DataType> {
 fn task1(data: &DataType) -> Result<ResultType> {
     let (wire1, wire2) = data;
 
-    let wire1_positions: HashSet<Position> = wire1.positions().collect();
+    let wire1_positions: HashSet<Point> = wire1.positions().collect();
 
     wire2
         .positions()
         .filter_map(|pos| {
-            if !pos.is_origin() && wire1_positions.contains(&pos) {
+            if pos != Point::ORIGIN && wire1_positions.contains(&pos) {
                 Some(pos.manhattan())
             } else {
                 None
@@ -168,12 +142,12 @@ fn task1(data: &DataType) -> Result<ResultType> {
 fn task2(data: &DataType) -> Result<ResultType> {
     let (wire1, wire2) = data;
 
-    let wire1_positions: HashMap<Position, u64> = wire1.positions_distance().collect();
+    let wire1_positions: HashMap<Point, u64> = wire1.positions_distance().collect();
 
     wire2
         .positions_distance()
         .filter_map(|(pos2, dist2)| {
-            if pos2.is_origin() {
+            if pos2 == Point::ORIGIN {
                 return None;
             }
 
@@ -1,10 +1,15 @@
 use std::str::FromStr;
 
-use aoc::{AoCError, AoCInput};
+use aoc::{
+    AoCError, AoCInput,
+    utils::{Position, Size, Vec2d, ocr},
+    vm::{self, Flow, Instruction as _, Machine},
+};
 
+#[derive(Clone)]
 enum Instruction {
     Noop,
-    AddX(i32),
+    AddX(i64),
 }
 
 impl FromStr for Instruction {
@@ -15,41 +20,63 @@ impl FromStr for Instruction {
 
         match instr.next().unwrap() {
             "noop" => Ok(Self::Noop),
-            "addx" => Ok(Self::AddX(instr.next().unwrap().parse::<i32>()?)),
+            "addx" => Ok(Self::AddX(instr.next().unwrap().parse::<i64>()?)),
             _ => Err(AoCError::BadInput),
         }
     }
 }
 
-fn solve_task(input: &str) -> (i32, Vec<i32>) {
-    let instructions: Vec<Instruction> = input.lines().map(|i| i.parse().unwrap()).collect();
-
-    // Task 1
-    const INTERESTING: [usize; 6] = [20, 60, 100, 140, 180, 220];
-    let mut reg_value: Vec<i32> = Vec::new();
-    let mut reg = 1;
-    for i in instructions.iter().cycle() {
-        match i {
-            Instruction::Noop => reg_value.push(reg),
-            Instruction::AddX(x) => {
-                reg_value.push(reg);
-                reg_value.push(reg);
-                reg += x;
-            }
+impl vm::Instruction for Instruction {
+    fn execute(&self, machine: &mut Machine<Self>) -> Flow {
+        if let Self::AddX(x) = self {
+            machine.registers[0] += x;
         }
+        Flow::Next
+    }
 
-        if reg_value.len() > 240 {
-            break;
+    fn cycles(&self) -> u64 {
+        match self {
+            Self::Noop => 1,
+            Self::AddX(_) => 2,
         }
     }
+}
+
+fn solve_task(input: &str) -> (i64, Vec<i64>) {
+    let mut cpu: Machine<Instruction> = Machine::parse(input, 1).unwrap();
+    cpu.registers[0] = 1;
+
+    // The value of X during each cycle, the program repeats until the screen is drawn
+    let mut reg_value: Vec<i64> = Vec::new();
+    while reg_value.len() < 240 && !cpu.program.is_empty() {
+        cpu.goto(0);
+        cpu.run_traced(|trace| {
+            for _ in 0..trace.instruction.cycles() {
+                reg_value.push(trace.registers[0]);
+            }
+        });
+    }
+
+    // Task 1
+    const INTERESTING: [usize; 6] = [20, 60, 100, 140, 180, 220];
     let mut task1 = 0;
     for &i in INTERESTING.iter() {
-        task1 += i as i32 * reg_value[i - 1];
+        task1 += i as i64 * reg_value[i - 1];
     }
 
     (task1, reg_value)
 }
 
+/// The pixels lit while the sprite covers the pixel being drawn.
+fn screen(reg_value: &[i64]) -> Vec2d<bool> {
+    let mut screen = Vec2d::filled(Size::new(40, 6), false).unwrap();
+    for (cycle, reg) in reg_value.iter().take(240).enumerate() {
+        let col = (cycle % 40) as i64;
+        screen[Position::new(cycle % 40, cycle / 40)] = (col - reg).abs() <= 1;
+    }
+    screen
+}
+
 fn main() {
     let input = AoCInput::from_env()
         .expect("SESSION env variable not found")
@@ -57,19 +84,16 @@ fn main() {
         .expect("Could not fetch input");
 
     let (task1, task2) = solve_task(&input);
+    let screen = screen(&task2);
 
     println!("Task 1: {}", task1);
-    println!("Task 2:");
-    for row in 0..6 {
-        for col in 0..40 {
-            let reg = task2[40 * row + col];
-            if (col as i32) - 1 <= reg && (col as i32) + 1 >= reg {
-                print!("█");
-            } else {
-                print!(" ");
-            }
+    match ocr(&screen) {
+        Ok(letters) => println!("Task 2: {}", letters),
+        Err(err) => {
+            println!("Task 2:");
+            print!("{}", screen.map(|lit| if *lit { '█' } else { ' ' }));
+            eprintln!("{}", err);
         }
-        println!();
     }
 }
 
@@ -226,9 +250,13 @@ noop
 noop
 noop"#;
 
-        let (example1, _example2) = solve_task(example_input);
+        let (example1, example2) = solve_task(example_input);
 
         assert_eq!(example1, 13140);
-        //assert_eq!(example2, 0);
+        let screen = screen(&example2).map(|lit| if *lit { '#' } else { '.' });
+        assert_eq!(
+            screen.to_string().lines().next(),
+            Some("##..##..##..##..##..##..##..##..##..##..")
+        );
     }
 }
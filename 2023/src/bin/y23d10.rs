@@ -1,4 +1,7 @@
-use aoc::{AoCError, AoCInput};
+use aoc::{
+    AoCError, AoCInput,
+    utils::{Direction, Size, Vec2d},
+};
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -11,8 +14,6 @@ enum Pipe {
     SouthEast,
     Ground,
     Start,
-    Inside,
-    Outside,
 }
 
 impl From<char> for Pipe {
@@ -42,8 +43,6 @@ impl From<&Pipe> for char {
             Pipe::SouthEast => '┌',
             Pipe::Ground => '.',
             Pipe::Start => 'S',
-            Pipe::Inside => 'I',
-            Pipe::Outside => 'O',
         }
     }
 }
@@ -60,6 +59,18 @@ impl Pipe {
             _ => None,
         }
     }
+
+    fn directions(&self) -> &'static [Direction] {
+        match self {
+            Pipe::NorthSouth => &[Direction::North, Direction::South],
+            Pipe::EastWest => &[Direction::East, Direction::West],
+            Pipe::NorthEast => &[Direction::North, Direction::East],
+            Pipe::NorthWest => &[Direction::North, Direction::West],
+            Pipe::SouthWest => &[Direction::South, Direction::West],
+            Pipe::SouthEast => &[Direction::South, Direction::East],
+            _ => &[],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -234,54 +245,24 @@ impl Iterator for MazeWalker<'_> {
     }
 }
 
-enum PipeState {
-    NotOn,
-    OnFromNorth,
-    OnFromSouth,
-}
-
-fn anotate_inside_outside(maze: &mut [Vec<Pipe>]) {
-    for line in maze.iter_mut() {
-        let mut pipe_state = PipeState::NotOn;
-        let mut inside = false;
-        for p in line.iter_mut() {
-            *p = match (&p, &pipe_state, inside) {
-                (Pipe::Ground, _, false) => Pipe::Outside,
-                (Pipe::Ground, _, true) => Pipe::Inside,
-                (Pipe::NorthSouth, _, io) => {
-                    inside = !io;
-                    Pipe::NorthSouth
-                }
-                (Pipe::NorthEast, PipeState::NotOn, _) => {
-                    pipe_state = PipeState::OnFromNorth;
-                    Pipe::NorthEast
-                }
-                (Pipe::SouthEast, PipeState::NotOn, _) => {
-                    pipe_state = PipeState::OnFromSouth;
-                    Pipe::SouthEast
-                }
-                (Pipe::NorthWest, PipeState::OnFromSouth, io) => {
-                    inside = !io;
-                    pipe_state = PipeState::NotOn;
-                    Pipe::NorthWest
-                }
-                (Pipe::NorthWest, _, _) => {
-                    pipe_state = PipeState::NotOn;
-                    Pipe::NorthWest
-                }
-                (Pipe::SouthWest, PipeState::OnFromNorth, io) => {
-                    inside = !io;
-                    pipe_state = PipeState::NotOn;
-                    Pipe::SouthWest
-                }
-                (Pipe::SouthWest, _, _) => {
-                    pipe_state = PipeState::NotOn;
-                    Pipe::SouthWest
-                }
-                _ => *p,
-            }
+/// Counts the tiles enclosed by the loop. The loop is drawn at three times the size, so the
+/// gaps between adjacent pipes become tiles the outside can be flooded through.
+fn count_inside(maze: &Maze, maze_loop: &[(Position, Pipe)]) -> usize {
+    let size = Size::new(maze.map[0].len() * 3, maze.map.len() * 3);
+    let mut walls = Vec2d::filled(size, false).unwrap();
+    for (pos, pipe) in maze_loop {
+        let centre = aoc::utils::Position::new(pos.east * 3 + 1, pos.south * 3 + 1);
+        walls[centre] = true;
+        for direction in pipe.directions() {
+            walls[centre + *direction] = true;
         }
     }
+
+    let enclosed = walls.enclosed(|wall| *wall);
+    enclosed
+        .iter()
+        .filter(|(pos, enclosed)| **enclosed && pos.x % 3 == 1 && pos.y % 3 == 1)
+        .count()
 }
 
 fn solve_task(input: &str) -> (usize, usize) {
@@ -289,31 +270,8 @@ fn solve_task(input: &str) -> (usize, usize) {
 
     let maze_loop = maze.iter().collect::<Vec<_>>();
 
-    // Create a new map with only the loop set
-    let mut new_map: Vec<Vec<Pipe>> = vec![vec![Pipe::Ground; maze.map[0].len()]; maze.map.len()];
-    for (pos, pipe) in maze_loop.iter() {
-        new_map[pos.south][pos.east] = *pipe;
-    }
-
-    // Anotate inside and outside
-    anotate_inside_outside(&mut new_map);
-
-    // Print maze
-    // for line in new_map.iter() {
-    //     println!(
-    //         "{}",
-    //         line.iter()
-    //             .map(<&Pipe as Into<char>>::into)
-    //             .collect::<String>()
-    //     );
-    // }
-
     let task1 = maze_loop.len() / 2;
-    let task2 = new_map
-        .iter()
-        .flatten()
-        .filter(|p| **p == Pipe::Inside)
-        .count();
+    let task2 = count_inside(&maze, &maze_loop);
 
     (task1, task2)
 }
@@ -41,7 +41,7 @@ struct Pattern {
 
 impl Display for Pattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.original.data() {
+        for row in self.original.rows() {
             for m in row {
                 write!(
                     f,
@@ -71,8 +71,9 @@ impl Pattern {
     fn find_reflections(&self) -> Vec<Reflection> {
         let mut reflections = Vec::new();
         // Look for horizontal reflection
+        let rows: Vec<_> = self.original.rows().collect();
         for i in 1..self.original.height() {
-            let (first, last) = self.original.data().split_at(i);
+            let (first, last) = rows.split_at(i);
 
             if first.iter().rev().zip(last.iter()).all(|(a, b)| a == b) {
                 reflections.push(Reflection::Horizontal(i));
@@ -80,8 +81,9 @@ impl Pattern {
         }
 
         // Look for vertical reflection
+        let rows: Vec<_> = self.transposed.rows().collect();
         for i in 1..self.transposed.height() {
-            let (first, last) = self.transposed.data().split_at(i);
+            let (first, last) = rows.split_at(i);
 
             if first.iter().rev().zip(last.iter()).all(|(a, b)| a == b) {
                 reflections.push(Reflection::Vertical(i));
@@ -0,0 +1,226 @@
+//! Compares `Vec2d` with the nested `Vec<Vec<T>>` layout it used to have, running the 2024
+//! day 6 (a guard walking until it leaves or loops) and day 15 (a robot pushing boxes)
+//! solutions on generated inputs of the size of the real ones. Run with
+//! `cargo bench -p aoc2024 --bench vec2d`.
+
+#[path = "../../aoc/benches/common/mod.rs"]
+mod common;
+
+use common::bench;
+use std::hint::black_box;
+
+/// Compiles a day's solution into a module with a `solve` function running both parts. An
+/// extra `use` replaces the `Vec2d` the solution gets from `aoc::utils`.
+macro_rules! solution {
+    ($name:ident, $path:literal $(, $grid:item)?) => {
+        pub mod $name {
+            // `main` and the imports of the binary's tests are unused here
+            #![allow(dead_code, unused_imports)]
+            $($grid)?
+
+            pub fn solve(input: &str) -> (usize, usize) {
+                let data = parse(input).unwrap();
+                (task1(&data).unwrap(), task2(&data).unwrap())
+            }
+
+            include!($path);
+        }
+    };
+}
+
+mod flat {
+    solution!(y24d06, "../src/bin/y24d06.rs");
+    solution!(y24d15, "../src/bin/y24d15.rs");
+}
+
+mod nested {
+    use aoc::{
+        AoCError,
+        utils::{Position, Size, Vec2dError},
+    };
+    use std::{iter::Flatten, slice, str::FromStr};
+
+    solution!(y24d06, "../src/bin/y24d06.rs", use super::Vec2d;);
+    solution!(y24d15, "../src/bin/y24d15.rs", use super::Vec2d;);
+
+    /// The previous `Vec2d`, reduced to what the two solutions use
+    #[derive(Debug, Clone)]
+    pub struct Vec2d<T> {
+        data: Vec<Vec<T>>,
+        size: Size,
+    }
+
+    impl<T> Vec2d<T> {
+        pub fn new(data: Vec<Vec<T>>) -> Result<Self, Vec2dError> {
+            let width = data.first().map_or(0, |row| row.len());
+            if width == 0 {
+                Err(Vec2dError::Empty)
+            } else if data.iter().any(|row| row.len() != width) {
+                Err(Vec2dError::NotSquare)
+            } else {
+                let size = Size::new(width, data.len());
+                Ok(Self { data, size })
+            }
+        }
+
+        pub fn size(&self) -> &Size {
+            &self.size
+        }
+
+        pub fn get(&self, pos: Position) -> Option<&T> {
+            self.data.get(pos.y)?.get(pos.x)
+        }
+
+        pub fn set(&mut self, pos: Position, item: T) -> Result<(), Vec2dError> {
+            if !self.size.contains(pos) {
+                return Err(Vec2dError::OutOfBounds);
+            }
+
+            self.data[pos.y][pos.x] = item;
+            Ok(())
+        }
+
+        pub fn flatten(&self) -> Flatten<slice::Iter<'_, Vec<T>>> {
+            self.data.iter().flatten()
+        }
+
+        pub fn rows(&self) -> slice::Iter<'_, Vec<T>> {
+            self.data.iter()
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+            self.size.iter().map(|pos| (pos, &self.data[pos.y][pos.x]))
+        }
+
+        pub fn find(&self, item: &T) -> Option<(Position, &T)>
+        where
+            T: Eq,
+        {
+            self.iter().find(|(_, i)| *i == item)
+        }
+
+        pub fn swap(&mut self, src: Position, dst: Position) -> Result<(), Vec2dError>
+        where
+            T: Copy,
+        {
+            if !self.size.contains(src) || !self.size.contains(dst) {
+                return Err(Vec2dError::OutOfBounds);
+            }
+
+            let tmp = self.data[src.y][src.x];
+            self.data[src.y][src.x] = self.data[dst.y][dst.x];
+            self.data[dst.y][dst.x] = tmp;
+            Ok(())
+        }
+    }
+
+    impl<T> FromStr for Vec2d<T>
+    where
+        T: TryFrom<char, Error = AoCError>,
+    {
+        type Err = AoCError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let data = s
+                .lines()
+                .map(|line| line.chars().map(T::try_from).collect())
+                .collect::<Result<Vec<Vec<T>>, Self::Err>>()?;
+
+            Ok(Self::new(data)?)
+        }
+    }
+}
+
+/// A deterministic pseudo random sequence
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+}
+
+/// A `size` by `size` map with `percent` of the tiles `item` and `start` in the middle.
+fn random_map(lcg: &mut Lcg, size: usize, item: char, percent: u64, start: char) -> String {
+    let mut map = String::new();
+    for y in 0..size {
+        for x in 0..size {
+            map.push(if (x, y) == (size / 2, size / 2) {
+                start
+            } else if lcg.next(100) < percent {
+                item
+            } else {
+                '.'
+            });
+        }
+        map.push('\n');
+    }
+    map
+}
+
+/// A 2024 day 6 input, a lab with a few obstructions.
+fn lab() -> String {
+    random_map(&mut Lcg(6), 130, '#', 2, '^')
+}
+
+/// A 2024 day 15 input, a walled warehouse full of boxes and the moves of the robot.
+fn warehouse() -> String {
+    let mut lcg = Lcg(15);
+    let size = 50;
+    let mut input: String = random_map(&mut lcg, size, 'O', 30, '@')
+        .lines()
+        .enumerate()
+        .map(|(y, row)| {
+            let row: String = row
+                .chars()
+                .enumerate()
+                .map(|(x, tile)| {
+                    let border = x == 0 || y == 0 || x == size - 1 || y == size - 1;
+                    if border || (tile == '.' && lcg.next(100) < 5) {
+                        '#'
+                    } else {
+                        tile
+                    }
+                })
+                .collect();
+            row + "\n"
+        })
+        .collect();
+
+    input.push('\n');
+    for _ in 0..20 {
+        let moves: String = (0..1000)
+            .map(|_| ['^', '>', 'v', '<'][lcg.next(4) as usize])
+            .collect();
+        input.push_str(&moves);
+        input.push('\n');
+    }
+    input
+}
+
+fn main() {
+    let lab = lab();
+    assert_eq!(flat::y24d06::solve(&lab), nested::y24d06::solve(&lab));
+    bench("y24d06 guard, nested", 5, || {
+        black_box(nested::y24d06::solve(black_box(&lab)));
+    });
+    bench("y24d06 guard, flat", 5, || {
+        black_box(flat::y24d06::solve(black_box(&lab)));
+    });
+
+    let warehouse = warehouse();
+    assert_eq!(
+        flat::y24d15::solve(&warehouse),
+        nested::y24d15::solve(&warehouse)
+    );
+    bench("y24d15 warehouse, nested", 500, || {
+        black_box(nested::y24d15::solve(black_box(&warehouse)));
+    });
+    bench("y24d15 warehouse, flat", 500, || {
+        black_box(flat::y24d15::solve(black_box(&warehouse)));
+    });
+}
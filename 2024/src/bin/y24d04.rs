@@ -2,40 +2,12 @@ const YEAR: u16 = 2024;
 const DAY: u8 = 4;
 use aoc::utils::*;
 use aoc::*;
-use strum::IntoEnumIterator;
 
-trait Xmas {
-    fn get_word(&self, position: Position, direction: Direction) -> Option<[char; 4]>;
-    fn get_x(&self, position: Position) -> Option<([char; 3], [char; 3])>;
-}
-
-impl Xmas for Vec2d<char> {
-    fn get_word(&self, position: Position, direction: Direction) -> Option<[char; 4]> {
-        let mut word = [char::default(); 4];
-
-        let dist = Distance::from(direction);
-
-        for (i, c) in word.iter_mut().enumerate() {
-            *c = *self.get(position + dist * i)?;
-        }
-
-        Some(word)
-    }
-
-    fn get_x(&self, position: Position) -> Option<([char; 3], [char; 3])> {
-        let mut w1 = [char::default(); 3];
-        let mut w2 = [char::default(); 3];
-
-        w1[0] = *self.get(position + Direction::NorthWest)?;
-        w1[1] = *self.get(position)?;
-        w1[2] = *self.get(position + Direction::SouthEast)?;
-
-        w2[0] = *self.get(position + Direction::NorthEast)?;
-        w2[1] = w1[1];
-        w2[2] = *self.get(position + Direction::SouthWest)?;
-
-        Some((w1, w2))
-    }
+/// Number of placements of `template` in any orientation, `.` matches anything
+fn count(data: &Vec2d<char>, template: &[&str]) -> Result<usize> {
+    let template = Vec2d::new(template.iter().map(|row| row.chars().collect()).collect())?;
+    let pattern = Pattern::with_wildcard(&template, &'.');
+    Ok(pattern.find_oriented(data).len())
 }
 
 type ResultType = usize;
@@ -48,35 +20,11 @@ fn parse(input: &str) -> Result<DataType> {
 }
 
 fn task1(data: &DataType) -> Result<ResultType> {
-    let mut result = 0;
-
-    for pos in data.size().iter() {
-        if data.get(pos).is_some_and(|&c| c == 'X') {
-            result += Direction::iter()
-                .filter(|&dir| matches!(data.get_word(pos, dir), Some(['X', 'M', 'A', 'S'])))
-                .count();
-        }
-    }
-
-    Ok(result)
+    Ok(count(data, &["XMAS"])? + count(data, &["X...", ".M..", "..A.", "...S"])?)
 }
 
 fn task2(data: &DataType) -> Result<ResultType> {
-    let mut result = 0;
-
-    for pos in data.size().iter() {
-        if data.get(pos).is_some_and(|&c| c == 'A') {
-            result += match data.get_x(pos) {
-                Some((['M', 'A', 'S'], ['M', 'A', 'S'])) => 1,
-                Some((['M', 'A', 'S'], ['S', 'A', 'M'])) => 1,
-                Some((['S', 'A', 'M'], ['M', 'A', 'S'])) => 1,
-                Some((['S', 'A', 'M'], ['S', 'A', 'M'])) => 1,
-                _ => 0,
-            }
-        }
-    }
-
-    Ok(result)
+    count(data, &["M.S", ".A.", "M.S"])
 }
 
 fn main() -> Result<()> {
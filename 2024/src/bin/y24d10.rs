@@ -9,38 +9,15 @@ type Trail = Vec<Position>;
 type ResultType = usize;
 type DataType = HashMap<Position, Vec<Trail>>;
 
-fn find_trails(map: &Vec2d<u8>, trail: &mut Trail) -> Vec<Trail> {
-    const DIRECTIONS: [Direction; 4] = [
-        Direction::North,
-        Direction::East,
-        Direction::South,
-        Direction::West,
-    ];
-
-    let mut trails = vec![];
-
-    #[allow(clippy::collapsible_if)]
-    if let Some(&pos) = trail.last() {
-        if let Some(&tile) = map.get(pos) {
-            for &dir in DIRECTIONS.iter() {
-                let next = pos + dir;
-
-                if let Some(&next_tile) = map.get(next) {
-                    if next_tile == tile + 1 {
-                        let mut trail = trail.clone();
-                        trail.push(next);
-                        if next_tile == 9 {
-                            trails.push(trail);
-                        } else {
-                            trails.extend(find_trails(map, &mut trail));
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    trails
+fn find_trails(map: &Vec2d<u8>, trailhead: Position) -> Vec<Trail> {
+    let paths = map.bfs(trailhead, |from, to| *to == from + 1);
+
+    paths
+        .distances()
+        .keys()
+        .filter(|pos| map[**pos] == 9)
+        .flat_map(|pos| paths.all_paths(pos))
+        .collect()
 }
 
 fn parse(input: &str) -> Result<DataType> {
@@ -56,14 +33,8 @@ fn parse(input: &str) -> Result<DataType> {
 
     let trails = map
         .iter()
-        .filter_map(|(pos, &tile)| {
-            if tile == 0 {
-                let mut trail = vec![pos];
-                Some((pos, find_trails(&map, &mut trail)))
-            } else {
-                None
-            }
-        })
+        .filter(|(_, tile)| **tile == 0)
+        .map(|(pos, _)| (pos, find_trails(&map, pos)))
         .collect();
 
     Ok(trails)
@@ -3,159 +3,31 @@ const DAY: u8 = 12;
 
 use aoc::utils::*;
 use aoc::*;
-use std::collections::HashSet;
-
-const DIRECTIONS: [Direction; 4] = [
-    Direction::North,
-    Direction::East,
-    Direction::South,
-    Direction::West,
-];
-
-struct Region {
-    id: char,
-    positions: HashSet<Position>,
-}
-
-impl Region {
-    fn new(id: char) -> Self {
-        Self {
-            id,
-            positions: HashSet::new(),
-        }
-    }
-
-    fn add(&mut self, position: Position) {
-        self.positions.insert(position);
-    }
-
-    fn contains(&self, position: &Position) -> bool {
-        self.positions.contains(position)
-    }
-
-    fn size(&self) -> usize {
-        self.positions.len()
-    }
-
-    fn perimeter(&self) -> usize {
-        let mut perimeter = 0;
-        for &position in self.positions.iter() {
-            for direction in DIRECTIONS {
-                let neighbour = position + direction;
-                if !self.contains(&neighbour) {
-                    perimeter += 1;
-                }
-            }
-        }
-        perimeter
-    }
-
-    fn sides(&self) -> usize {
-        // Count the number of corners instead of sides, much easier
-        let mut corners = 0;
-
-        for &position in self.positions.iter() {
-            let north = position + Direction::North;
-            let east = position + Direction::East;
-            let south = position + Direction::South;
-            let west = position + Direction::West;
-
-            // Outer corners
-            if !self.contains(&north) && !self.contains(&west) {
-                corners += 1;
-            }
-            if !self.contains(&north) && !self.contains(&east) {
-                corners += 1;
-            }
-            if !self.contains(&south) && !self.contains(&east) {
-                corners += 1;
-            }
-            if !self.contains(&south) && !self.contains(&west) {
-                corners += 1;
-            }
-
-            let north_east = position + Direction::NorthEast;
-            let north_west = position + Direction::NorthWest;
-            let south_east = position + Direction::SouthEast;
-            let south_west = position + Direction::SouthWest;
-
-            // Inner corners
-            if self.contains(&north) && self.contains(&east) && !self.contains(&north_east) {
-                corners += 1;
-            }
-            if self.contains(&north) && self.contains(&west) && !self.contains(&north_west) {
-                corners += 1;
-            }
-            if self.contains(&south) && self.contains(&east) && !self.contains(&south_east) {
-                corners += 1;
-            }
-            if self.contains(&south) && self.contains(&west) && !self.contains(&south_west) {
-                corners += 1;
-            }
-        }
-
-        corners
-    }
-}
 
 type ResultType = usize;
 type DataType = Vec<Region>;
 
-fn find_region(map: &Vec2d<char>, position: Position, region: &mut Region) {
-    const DIRECTIONS: [Direction; 4] = [
-        Direction::North,
-        Direction::East,
-        Direction::South,
-        Direction::West,
-    ];
-
-    for direction in DIRECTIONS {
-        let neighbour = position + direction;
-        if let Some(&id) = map.get(neighbour)
-            && id == region.id
-            && !region.contains(&neighbour)
-        {
-            region.add(neighbour);
-            find_region(map, neighbour, region);
-        }
-    }
-}
-
 fn parse(input: &str) -> Result<DataType> {
     let data = input
         .lines()
         .map(|line| line.chars().collect())
         .collect::<Vec<_>>();
-    let data = Vec2d::new(data)?;
-
-    let mut regions: Vec<Region> = vec![];
-
-    for (position, &id) in data.iter() {
-        if regions.iter().any(|region| region.contains(&position)) {
-            // Already in a region
-            continue;
-        }
-
-        let mut region = Region::new(id);
-        region.add(position);
-        find_region(&data, position, &mut region);
-        regions.push(region);
-    }
+    let data: Vec2d<char> = Vec2d::new(data)?;
 
-    Ok(regions)
+    Ok(data.regions(|a, b| a == b).into_regions())
 }
 
 fn task1(data: &DataType) -> Result<ResultType> {
     Ok(data
         .iter()
-        .map(|region| region.size() * region.perimeter())
+        .map(|region| region.area() * region.perimeter())
         .sum())
 }
 
 fn task2(data: &DataType) -> Result<ResultType> {
     Ok(data
         .iter()
-        .map(|region| region.size() * region.sides())
+        .map(|region| region.area() * region.sides())
         .sum())
 }
 
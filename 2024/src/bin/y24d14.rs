@@ -1,14 +1,14 @@
 const YEAR: u16 = 2024;
 const DAY: u8 = 14;
 
-use aoc::*;
+use aoc::{utils::Point, *};
 use itertools::Itertools;
 use nom::{
+    IResult,
     bytes::complete::tag,
     character::complete::{self, newline, space1},
     multi::separated_list1,
     sequence::{preceded, separated_pair},
-    IResult,
 };
 
 struct Room {
@@ -16,52 +16,31 @@ struct Room {
     height: i64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Position {
-    x: i64,
-    y: i64,
-}
-
-#[derive(Debug, Clone)]
-struct Velocity {
-    x: i64,
-    y: i64,
-}
-
 #[derive(Debug, Clone)]
 struct Robot {
-    position: Position,
-    velocity: Velocity,
-}
-
-fn position(input: &str) -> IResult<&str, Position> {
-    let (input, (x, y)) = preceded(
-        tag("p="),
-        separated_pair(complete::i64, tag(","), complete::i64),
-    )(input)?;
-    Ok((input, Position { x, y }))
+    position: Point,
+    velocity: Point,
 }
 
-fn velocity(input: &str) -> IResult<&str, Velocity> {
-    let (input, (x, y)) = preceded(
-        tag("v="),
-        separated_pair(complete::i64, tag(","), complete::i64),
-    )(input)?;
-    Ok((input, Velocity { x, y }))
+fn point<'a>(prefix: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, Point> {
+    move |input| {
+        let (input, (x, y)) = preceded(
+            tag(prefix),
+            separated_pair(complete::i64, tag(","), complete::i64),
+        )(input)?;
+        Ok((input, Point::new(x, y)))
+    }
 }
 
 fn robot(input: &str) -> IResult<&str, Robot> {
-    let (input, (position, velocity)) = separated_pair(position, space1, velocity)(input)?;
+    let (input, (position, velocity)) = separated_pair(point("p="), space1, point("v="))(input)?;
     Ok((input, Robot { position, velocity }))
 }
 
 impl Robot {
     fn move_seconds(&mut self, seconds: i64, room: &Room) {
-        let x = self.position.x + self.velocity.x * seconds;
-        let y = self.position.y + self.velocity.y * seconds;
-
-        self.position.x = x.rem_euclid(room.width);
-        self.position.y = y.rem_euclid(room.height);
+        self.position = (self.position + self.velocity * seconds)
+            .rem_euclid(Point::new(room.width, room.height));
     }
 }
 
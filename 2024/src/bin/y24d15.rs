@@ -63,7 +63,7 @@ impl Warehouse {
     fn expanded(&self) -> Self {
         let mut data = vec![];
 
-        for line in self.map.data() {
+        for line in self.map.rows() {
             let mut new_line = vec![];
 
             for tile in line {
@@ -104,6 +104,7 @@ impl Warehouse {
         }
     }
 
+    #[allow(clippy::collapsible_match)]
     fn move_robot(&mut self, direction: Direction) {
         let new_pos = self.robot + direction;
 
@@ -1,7 +1,6 @@
 const YEAR: u16 = 2025;
 const DAY: u8 = 4;
 use aoc::{utils::*, *};
-use strum::IntoEnumIterator;
 
 type ResultType = usize;
 type DataType = Vec2d<bool>;
@@ -23,13 +22,7 @@ fn task1(data: &DataType) -> Result<ResultType> {
                 return false;
             }
 
-            Direction::iter()
-                .filter(|&dir| {
-                    let pos = *pos + dir;
-                    data.get(pos).is_some_and(|roll| *roll)
-                })
-                .count()
-                < 4
+            data.neighbours8(*pos).filter(|(_, roll)| **roll).count() < 4
         })
         .count())
 }
@@ -46,11 +39,9 @@ fn task2(data: &DataType) -> Result<ResultType> {
                     return None;
                 }
 
-                if Direction::iter()
-                    .filter(|&dir| {
-                        let pos = pos + dir;
-                        warehouse.get(pos).is_some_and(|roll| *roll)
-                    })
+                if warehouse
+                    .neighbours8(pos)
+                    .filter(|(_, roll)| **roll)
                     .count()
                     < 4
                 {
@@ -1,7 +1,7 @@
 const YEAR: u16 = 2025;
 const DAY: u8 = 5;
 
-use aoc::{utils::*, *};
+use aoc::{utils::Parser, *};
 use std::{ops::RangeInclusive, str::FromStr};
 
 #[derive(Debug, Clone)]
@@ -1,41 +1,11 @@
-use aoc::{problem::*, utils::*, *};
-use std::{
-    collections::{BTreeMap, HashSet},
-    hash::Hash,
-    ops::Sub,
-    str::FromStr,
+use aoc::{
+    problem::*,
+    utils::{Parser, *},
+    *,
 };
+use std::collections::{BTreeMap, HashSet};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct JunctionBox {
-    x: i64,
-    y: i64,
-    z: i64,
-}
-
-impl FromStr for JunctionBox {
-    type Err = AoCError;
-
-    fn from_str(s: &str) -> Result<Self> {
-        let mut s = s.split(',');
-
-        let x = s.next().ok_or(AoCError::BadInput)?.parse()?;
-        let y = s.next().ok_or(AoCError::BadInput)?.parse()?;
-        let z = s.next().ok_or(AoCError::BadInput)?.parse()?;
-
-        Ok(Self::new(x, y, z))
-    }
-}
-
-impl JunctionBox {
-    fn new(x: i64, y: i64, z: i64) -> Self {
-        Self { x, y, z }
-    }
-
-    fn distance_to_squared(&self, other: &JunctionBox) -> i64 {
-        self.x.sub(other.x).pow(2) + self.y.sub(other.y).pow(2) + self.z.sub(other.z).pow(2)
-    }
-}
+type JunctionBox = Point3;
 
 #[derive(Debug, Default)]
 struct Circuit(HashSet<JunctionBox>);
@@ -88,7 +58,7 @@ impl Circuits {
 #[derive(Default)]
 struct Problem {
     boxes: Vec<JunctionBox>,
-    distances: BTreeMap<i64, (JunctionBox, JunctionBox)>,
+    distances: BTreeMap<u64, (JunctionBox, JunctionBox)>,
 }
 
 impl AoCProblem<usize, i64> for Problem {
@@ -101,7 +71,7 @@ impl AoCProblem<usize, i64> for Problem {
 
         self.distances.clear();
         for (b1, b2) in self.boxes.pairs() {
-            self.distances.insert(b1.distance_to_squared(&b2), (b1, b2));
+            self.distances.insert(b1.euclidean_squared(&b2), (b1, b2));
         }
 
         Ok(())
@@ -131,7 +101,7 @@ impl AoCProblem<usize, i64> for Problem {
             circuits.connect(*b1, *b2);
 
             if circuits.len() == 1 && circuits.0[0].len() == self.boxes.len() {
-                return Ok(b1.x * b2.x);
+                return Ok(b1.x() * b2.x());
             }
         }
 
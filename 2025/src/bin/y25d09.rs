@@ -1,4 +1,8 @@
-use aoc::{problem::*, utils::*, *};
+use aoc::{
+    problem::*,
+    utils::{Parser, *},
+    *,
+};
 
 #[derive(Debug)]
 enum Line {
@@ -1,4 +1,4 @@
-use aoc::{problem::*, utils::*, *};
+use aoc::{problem::*, utils::Parser, *};
 use microlp::{ComparisonOp, LinearExpr, OptimizationDirection, Problem as LpProblem};
 use std::{collections::HashSet, str::FromStr};
 
@@ -1,4 +1,8 @@
-use aoc::{problem::*, utils::*, *};
+use aoc::{
+    problem::*,
+    utils::{Parser, *},
+    *,
+};
 use std::str::FromStr;
 
 #[derive(Debug)]
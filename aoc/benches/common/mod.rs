@@ -0,0 +1,21 @@
+//! Timing shared by the benches, which run without a harness.
+
+use std::time::Instant;
+
+pub fn bench(name: &str, iterations: u32, mut f: impl FnMut()) {
+    // Warm up caches and the branch predictor first
+    for _ in 0..iterations.div_ceil(10) {
+        f();
+    }
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{name:<32} {:>12.2?} per iteration ({iterations} iterations)",
+        elapsed / iterations
+    );
+}
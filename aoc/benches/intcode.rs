@@ -0,0 +1,61 @@
+//! Compares the Intcode CPU with and without its decoded instruction cache, and restoring a
+//! snapshot with cloning a CPU. Run with `cargo bench -p aoc --bench intcode`.
+
+mod common;
+
+use aoc::intcode::{CPU, Program};
+use common::bench;
+use std::hint::black_box;
+
+/// Sums the numbers from the input down to 1
+const SUM: &str = "
+          in n
+    loop: add sum, n, sum
+          add n, #-1, n
+          jnz n, #loop
+          out sum
+          hlt
+    n:    data 0
+    sum:  data 0
+";
+
+fn run(cpu: &mut CPU, input: i64) -> i64 {
+    cpu.push_input(input);
+    cpu.run().unwrap();
+    cpu.take_output()[0]
+}
+
+fn main() {
+    let program = Program::assemble(SUM).unwrap();
+
+    // A long running loop
+    for cache in [false, true] {
+        let mut cpu = CPU::new(program.clone());
+        cpu.set_decode_cache(cache);
+        let snapshot = cpu.snapshot();
+
+        let name = if cache {
+            "loop, cached"
+        } else {
+            "loop, decoding"
+        };
+        bench(name, 20, || {
+            cpu.restore(&snapshot);
+            black_box(run(&mut cpu, black_box(1_000_000)));
+        });
+    }
+
+    // Many short runs from the same state, like the 2019 day 2 noun/verb search
+    let original = CPU::new(program);
+    bench("short runs, clone", 10_000, || {
+        let mut cpu = original.clone();
+        black_box(run(&mut cpu, black_box(10)));
+    });
+
+    let snapshot = original.snapshot();
+    let mut cpu = original.clone();
+    bench("short runs, restore", 10_000, || {
+        cpu.restore(&snapshot);
+        black_box(run(&mut cpu, black_box(10)));
+    });
+}
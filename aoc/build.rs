@@ -0,0 +1,37 @@
+//! Generates the `aoc::problem::EVENTS` table from `events.txt`.
+use std::{env, fs, path::Path};
+
+fn parse(line: &str) -> Option<(u16, u16, u8)> {
+    let (years, days) = line.split_once(char::is_whitespace)?;
+    let (first, last) = years.split_once('-').unwrap_or((years, years));
+    Some((
+        first.parse().ok()?,
+        last.parse().ok()?,
+        days.trim().parse().ok()?,
+    ))
+}
+
+fn main() {
+    println!("cargo::rerun-if-changed=events.txt");
+
+    let events = fs::read_to_string("events.txt").expect("Could not read events.txt");
+    let mut table = String::from("&[\n");
+    for (number, line) in events.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (first, last, days) = parse(line).unwrap_or_else(|| {
+            panic!(
+                "events.txt:{}: expected `<year> <days>` or `<first>-<last> <days>`",
+                number + 1
+            )
+        });
+        table.push_str(&format!("    ({first}..={last}, {days}),\n"));
+    }
+    table.push(']');
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out_dir).join("events.rs"), table).expect("Could not write events.rs");
+}
@@ -0,0 +1,311 @@
+use crate::problem::Date;
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    str::FromStr,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AnswersError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Unknown verdict {0}")]
+    UnknownVerdict(String),
+
+    #[error("Bad answer store entry: {0}")]
+    BadRecord(String),
+}
+
+type Result<T> = std::result::Result<T, AnswersError>;
+
+/// The verdict Advent of Code gave a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::TooHigh => write!(f, "too-high"),
+            Self::TooLow => write!(f, "too-low"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "wrong" => Ok(Self::Wrong),
+            "too-high" => Ok(Self::TooHigh),
+            "too-low" => Ok(Self::TooLow),
+            _ => Err(AnswersError::UnknownVerdict(s.to_string())),
+        }
+    }
+}
+
+/// The outcome of submitting an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    Verdict(Verdict),
+    /// Submitted too soon after a previous attempt, with the wait time if given
+    TooRecent(Option<String>),
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl Submission {
+    /// Interprets the HTML page returned when posting an answer.
+    pub fn from_response(html: &str) -> Self {
+        if html.contains("That's the right answer") {
+            Self::Verdict(Verdict::Correct)
+        } else if html.contains("your answer is too high") {
+            Self::Verdict(Verdict::TooHigh)
+        } else if html.contains("your answer is too low") {
+            Self::Verdict(Verdict::TooLow)
+        } else if html.contains("That's not the right answer") {
+            Self::Verdict(Verdict::Wrong)
+        } else if html.contains("You gave an answer too recently") {
+            let wait = html
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_string());
+            Self::TooRecent(wait)
+        } else if html.contains("You don't seem to be solving the right level") {
+            Self::AlreadySolved
+        } else {
+            let article = html
+                .split_once("<article>")
+                .and_then(|(_, rest)| rest.split_once("</article>"))
+                .map_or(html, |(article, _)| article);
+            Self::Unknown(article.trim().to_string())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub date: Date,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.date.year(),
+            self.date.day(),
+            self.part,
+            self.verdict,
+            self.answer
+        )
+    }
+}
+
+impl FromStr for Record {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let bad = || AnswersError::BadRecord(s.to_string());
+
+        let mut fields = s.splitn(5, '\t');
+        let mut field = || fields.next().ok_or_else(bad);
+
+        let year = field()?.parse().map_err(|_| bad())?;
+        let day = field()?.parse().map_err(|_| bad())?;
+        let part = field()?.parse().map_err(|_| bad())?;
+        let verdict = field()?.parse()?;
+        let answer = field()?.to_string();
+
+        Ok(Self {
+            date: Date::new(year, day).map_err(|_| bad())?,
+            part,
+            verdict,
+            answer,
+        })
+    }
+}
+
+impl Record {
+    /// Whether this verdict rules out `answer` without submitting it.
+    pub fn rules_out(&self, answer: &str) -> bool {
+        match self.verdict {
+            Verdict::Correct => false,
+            Verdict::Wrong => self.answer == answer,
+            Verdict::TooHigh => compare(answer, &self.answer).is_some_and(|c| c.is_ge()),
+            Verdict::TooLow => compare(answer, &self.answer).is_some_and(|c| c.is_le()),
+        }
+    }
+}
+
+fn compare(a: &str, b: &str) -> Option<std::cmp::Ordering> {
+    Some(a.parse::<i128>().ok()?.cmp(&b.parse::<i128>().ok()?))
+}
+
+/// Every answer submitted so far and its verdict, stored as a tab separated file.
+pub struct AnswerStore {
+    path: PathBuf,
+    records: Vec<Record>,
+}
+
+impl AnswerStore {
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join("answers.tsv");
+        let records = if path.exists() {
+            fs::read_to_string(&path)?
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(str::parse)
+                .collect::<Result<_>>()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { path, records })
+    }
+
+    pub fn records(&self, date: Date, part: u8) -> impl Iterator<Item = &Record> {
+        self.records
+            .iter()
+            .filter(move |r| r.date == date && r.part == part)
+    }
+
+    /// The accepted answer for a part, if any.
+    pub fn correct(&self, date: Date, part: u8) -> Option<&str> {
+        self.records(date, part)
+            .find(|r| r.verdict == Verdict::Correct)
+            .map(|r| r.answer.as_str())
+    }
+
+    /// Number of parts with an accepted answer.
+    pub fn stars(&self, date: Date) -> usize {
+        (1..=2)
+            .filter(|part| self.correct(date, *part).is_some())
+            .count()
+    }
+
+    /// The first earlier verdict that rules out `answer`, if any.
+    pub fn ruled_out_by(&self, date: Date, part: u8, answer: &str) -> Option<&Record> {
+        self.records(date, part).find(|r| r.rules_out(answer))
+    }
+
+    pub fn record(&mut self, record: Record) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{record}")?;
+
+        self.records.push(record);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(verdict: Verdict, answer: &str) -> Record {
+        Record {
+            date: Date::new(2019, 2).unwrap(),
+            part: 1,
+            verdict,
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_record_roundtrip() {
+        let record = record(Verdict::TooHigh, "1234");
+        let parsed: Record = record.to_string().parse().unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn test_bad_records() {
+        assert!(matches!(
+            "2019\t2\t1\tmaybe\t42".parse::<Record>(),
+            Err(AnswersError::UnknownVerdict(v)) if v == "maybe"
+        ));
+        assert!(matches!(
+            "2019\t2\t1\tcorrect".parse::<Record>(),
+            Err(AnswersError::BadRecord(_))
+        ));
+        assert!(matches!(
+            "2019\t26\t1\tcorrect\t42".parse::<Record>(),
+            Err(AnswersError::BadRecord(_))
+        ));
+    }
+
+    #[test]
+    fn test_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let date = Date::new(2019, 2).unwrap();
+
+        let mut store = AnswerStore::open(dir.path()).unwrap();
+        store.record(record(Verdict::TooLow, "10")).unwrap();
+        store.record(record(Verdict::Correct, "42 42")).unwrap();
+
+        let store = AnswerStore::open(dir.path()).unwrap();
+        assert_eq!(store.records(date, 1).count(), 2);
+        assert_eq!(store.correct(date, 1), Some("42 42"));
+        assert_eq!(store.stars(date), 1);
+        assert_eq!(
+            store.ruled_out_by(date, 1, "7").map(|r| r.verdict),
+            Some(Verdict::TooLow)
+        );
+
+        fs::write(dir.path().join("answers.tsv"), "2019\t2\t1\n").unwrap();
+        assert!(AnswerStore::open(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_rules_out() {
+        assert!(record(Verdict::Wrong, "abc").rules_out("abc"));
+        assert!(!record(Verdict::Wrong, "abc").rules_out("abd"));
+        assert!(record(Verdict::TooHigh, "100").rules_out("100"));
+        assert!(record(Verdict::TooHigh, "100").rules_out("150"));
+        assert!(!record(Verdict::TooHigh, "100").rules_out("99"));
+        assert!(record(Verdict::TooLow, "100").rules_out("-5"));
+        assert!(!record(Verdict::TooLow, "100").rules_out("101"));
+        assert!(!record(Verdict::Correct, "100").rules_out("100"));
+    }
+
+    #[test]
+    fn test_submission() {
+        assert_eq!(
+            Submission::from_response("<article><p>That's the right answer!</p></article>"),
+            Submission::Verdict(Verdict::Correct)
+        );
+        assert_eq!(
+            Submission::from_response(
+                "<p>That's not the right answer; your answer is too low.</p>"
+            ),
+            Submission::Verdict(Verdict::TooLow)
+        );
+        assert_eq!(
+            Submission::from_response(
+                "You gave an answer too recently. You have 38s left to wait."
+            ),
+            Submission::TooRecent(Some("38s".to_string()))
+        );
+    }
+}
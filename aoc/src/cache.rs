@@ -6,7 +6,9 @@ use std::{
 };
 use thiserror::Error;
 
-use crate::problem::Date;
+use crate::{answers::Submission, problem::Date};
+
+const USER_AGENT: &str = "github.com/LHelge/AdventOfCode";
 
 #[derive(Debug, Error)]
 pub enum CacheError {
@@ -18,13 +20,20 @@ pub enum CacheError {
 
     #[error("Problem fetching input: {0}")]
     FetchInput(String),
+
+    #[error("No session token available for fetching input")]
+    NoSession,
+
+    #[error("Invalid cached input: {0}")]
+    InvalidInput(&'static str),
 }
 
 type Result<T> = std::result::Result<T, CacheError>;
 
+/// Puzzle inputs stored as `yYYdDD.txt` files, fetched from Advent of Code on demand.
 pub struct Cache {
     dir: PathBuf,
-    session: String,
+    session: Option<String>,
 }
 
 impl Cache {
@@ -33,30 +42,196 @@ impl Cache {
         fs::create_dir_all(&dir)?;
         Ok(Self {
             dir,
-            session: session.into(),
+            session: Some(session.into()),
         })
     }
 
+    /// A cache that can only read, list and remove already stored inputs.
+    pub fn offline(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir, session: None })
+    }
+
+    pub fn path(&self, date: Date) -> PathBuf {
+        self.dir.join(date.filename())
+    }
+
+    pub fn contains(&self, date: Date) -> bool {
+        self.path(date).exists()
+    }
+
     pub fn get(&self, date: Date) -> Result<String> {
-        let filename = self.dir.join(date.filename());
+        let filename = self.path(date);
 
         if filename.exists() {
             Ok(fs::read_to_string(filename)?)
         } else {
-            let resp = Client::new()
-                .get(date.url())
-                .header("Cookie", format!("session={}", self.session))
-                .send()?;
-
-            if !resp.status().is_success() {
-                Err(CacheError::FetchInput(resp.text()?))
-            } else {
-                let input = resp.text()?.to_string();
-
-                let mut file = File::create(filename)?;
-                file.write_all(input.as_bytes())?;
-                Ok(input)
+            self.fetch(date)
+        }
+    }
+
+    /// Downloads the input, replacing any cached copy.
+    pub fn fetch(&self, date: Date) -> Result<String> {
+        let session = self.session.as_ref().ok_or(CacheError::NoSession)?;
+
+        let resp = Client::new()
+            .get(date.url())
+            .header("Cookie", format!("session={session}"))
+            .header("User-Agent", USER_AGENT)
+            .send()?;
+
+        if !resp.status().is_success() {
+            Err(CacheError::FetchInput(resp.text()?))
+        } else {
+            let input = resp.text()?.to_string();
+
+            let mut file = File::create(self.path(date))?;
+            file.write_all(input.as_bytes())?;
+            Ok(input)
+        }
+    }
+
+    /// The puzzle description page, fetched if it is not cached. The page is cached as
+    /// it was fetched, so part two only shows up after re-fetching once part one is solved.
+    pub fn puzzle(&self, date: Date) -> Result<String> {
+        if let Some(page) = self.cached_puzzle(date)? {
+            return Ok(page);
+        }
+
+        let session = self.session.as_ref().ok_or(CacheError::NoSession)?;
+        let resp = Client::new()
+            .get(date.puzzle_url())
+            .header("Cookie", format!("session={session}"))
+            .header("User-Agent", USER_AGENT)
+            .send()?;
+
+        if !resp.status().is_success() {
+            Err(CacheError::FetchInput(resp.text()?))
+        } else {
+            let page = resp.text()?.to_string();
+
+            let mut file = File::create(self.dir.join(date.puzzle_filename()))?;
+            file.write_all(page.as_bytes())?;
+            Ok(page)
+        }
+    }
+
+    pub fn cached_puzzle(&self, date: Date) -> Result<Option<String>> {
+        let filename = self.dir.join(date.puzzle_filename());
+
+        if filename.exists() {
+            Ok(Some(fs::read_to_string(filename)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Posts an answer for one part of a puzzle, nothing is cached.
+    pub fn submit(&self, date: Date, part: u8, answer: &str) -> Result<Submission> {
+        let session = self.session.as_ref().ok_or(CacheError::NoSession)?;
+        let level = part.to_string();
+
+        let resp = Client::new()
+            .post(date.answer_url())
+            .header("Cookie", format!("session={session}"))
+            .header("User-Agent", USER_AGENT)
+            .form(&[("level", level.as_str()), ("answer", answer)])
+            .send()?;
+
+        if !resp.status().is_success() {
+            Err(CacheError::FetchInput(resp.text()?))
+        } else {
+            Ok(Submission::from_response(&resp.text()?))
+        }
+    }
+
+    /// All dates with a cached input, in chronological order.
+    pub fn dates(&self) -> Result<Vec<Date>> {
+        let mut dates = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            if let Some(date) = entry?.file_name().to_str().and_then(Date::from_filename) {
+                dates.push(date);
             }
         }
+        dates.sort();
+        Ok(dates)
+    }
+
+    /// Removes a cached input, returns `false` if there was nothing to remove.
+    pub fn remove(&self, date: Date) -> Result<bool> {
+        let filename = self.path(date);
+
+        if filename.exists() {
+            fs::remove_file(filename)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Sanity checks a cached input, error pages and truncated downloads are rejected.
+    pub fn verify(&self, date: Date) -> Result<()> {
+        let input = fs::read_to_string(self.path(date))?;
+
+        if input.trim().is_empty() {
+            Err(CacheError::InvalidInput("empty"))
+        } else if input.contains("Please log in") || input.contains("Please don't repeatedly") {
+            Err(CacheError::InvalidInput("error page"))
+        } else if !input.ends_with('\n') {
+            Err(CacheError::InvalidInput("missing trailing newline"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::offline(dir.path()).unwrap();
+        let date = |day| Date::new(2024, day).unwrap();
+        let inputs = [
+            (1, "3   4\n4   3\n"),
+            (2, "\n"),
+            (
+                3,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            (4, "7 6 4 2 1\n1 2 7"),
+        ];
+        for (day, input) in inputs {
+            fs::write(cache.path(date(day)), input).unwrap();
+        }
+        fs::write(dir.path().join("y24d01.html"), "<html>").unwrap();
+
+        assert!(cache.verify(date(1)).is_ok());
+        assert!(matches!(
+            cache.verify(date(2)),
+            Err(CacheError::InvalidInput("empty"))
+        ));
+        assert!(matches!(
+            cache.verify(date(3)),
+            Err(CacheError::InvalidInput("error page"))
+        ));
+        assert!(matches!(
+            cache.verify(date(4)),
+            Err(CacheError::InvalidInput("missing trailing newline"))
+        ));
+        assert!(matches!(cache.verify(date(5)), Err(CacheError::Io(_))));
+
+        assert_eq!(
+            cache.dates().unwrap(),
+            (1..=4).map(date).collect::<Vec<_>>()
+        );
+        assert_eq!(cache.get(date(1)).unwrap(), "3   4\n4   3\n");
+        assert!(cache.remove(date(1)).unwrap());
+        assert!(!cache.remove(date(1)).unwrap());
+        assert!(!cache.contains(date(1)));
+        assert!(matches!(cache.get(date(1)), Err(CacheError::NoSession)));
     }
 }
@@ -32,6 +32,9 @@ pub enum AoCError {
     #[error(transparent)]
     Vec2d(#[from] crate::utils::Vec2dError),
 
+    #[error(transparent)]
+    Ocr(#[from] crate::utils::OcrError),
+
     #[error(transparent)]
     IntCode(#[from] crate::intcode::IntCodeError),
 
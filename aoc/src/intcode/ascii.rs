@@ -0,0 +1,148 @@
+use super::{CPU, Event, Program, Result};
+use std::io::{BufRead, Write};
+
+/// What an [`Ascii`] program printed before it halted or asked for input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Response {
+    /// Text output split into lines, without the newlines
+    pub lines: Vec<String>,
+    /// Output values outside the ASCII range, usually the puzzle answer
+    pub values: Vec<i64>,
+    pub halted: bool,
+}
+
+/// Drives an Intcode program that reads commands and prints text as ASCII codes.
+#[derive(Debug, Clone)]
+pub struct Ascii {
+    cpu: CPU,
+}
+
+impl From<CPU> for Ascii {
+    fn from(cpu: CPU) -> Self {
+        Self { cpu }
+    }
+}
+
+impl Ascii {
+    pub fn new(program: Program) -> Self {
+        Self::from(CPU::new(program))
+    }
+
+    pub fn cpu(&self) -> &CPU {
+        &self.cpu
+    }
+
+    pub fn cpu_mut(&mut self) -> &mut CPU {
+        &mut self.cpu
+    }
+
+    /// Queues a command, terminated by a newline unless it already ends with one. Carriage
+    /// returns are dropped so that `\r\n` line endings work too.
+    pub fn send(&mut self, command: &str) {
+        for c in command.chars().filter(|c| *c != '\r') {
+            self.cpu.push_input(c as i64);
+        }
+        if !command.ends_with('\n') {
+            self.cpu.push_input('\n' as i64);
+        }
+    }
+
+    /// Runs until the program halts or waits for input. A final line without a newline, like
+    /// a prompt, is returned as a line of its own.
+    pub fn run(&mut self) -> Result<Response> {
+        let mut response = Response::default();
+        let mut line = String::new();
+
+        loop {
+            match self.cpu.run_until_event()? {
+                Event::Output(10) => response.lines.push(std::mem::take(&mut line)),
+                Event::Output(value @ 0..=127) => line.push(value as u8 as char),
+                Event::Output(value) => response.values.push(value),
+                Event::NeedsInput => break,
+                Event::Halted => {
+                    response.halted = true;
+                    break;
+                }
+            }
+        }
+
+        if !line.is_empty() {
+            response.lines.push(line);
+        }
+        Ok(response)
+    }
+
+    /// Prints the program's output to `output` and sends it every line read from `input`,
+    /// until the program halts or the input ends. Use with stdin and stdout to explore a
+    /// program like 2019 day 25 by hand.
+    pub fn interactive(&mut self, mut input: impl BufRead, mut output: impl Write) -> Result<()> {
+        loop {
+            let response = self.run()?;
+            for line in &response.lines {
+                writeln!(output, "{line}")?;
+            }
+            for value in &response.values {
+                writeln!(output, "[{value}]")?;
+            }
+            output.flush()?;
+
+            if response.halted {
+                return Ok(());
+            }
+
+            let mut command = String::new();
+            if input.read_line(&mut command)? == 0 {
+                return Ok(());
+            }
+            self.send(&command);
+        }
+    }
+
+    /// [`Ascii::interactive`] on the terminal.
+    pub fn terminal(&mut self) -> Result<()> {
+        self.interactive(std::io::stdin().lock(), std::io::stdout())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Echoes every input value
+    const ECHO: &str = "3,100,4,100,1105,1,0";
+
+    #[test]
+    fn test_send() {
+        let mut ascii = Ascii::new(ECHO.parse().unwrap());
+        assert_eq!(ascii.run().unwrap(), Response::default());
+
+        ascii.send("WALK");
+        ascii.send("RUN\r\n");
+        let response = ascii.run().unwrap();
+        assert_eq!(response.lines, vec!["WALK", "RUN"]);
+        assert!(!response.halted);
+    }
+
+    #[test]
+    fn test_values() {
+        let mut ascii = Ascii::new("104,72,104,10,104,105,104,1000,99".parse().unwrap());
+        assert_eq!(
+            ascii.run().unwrap(),
+            Response {
+                lines: vec!["H".to_string(), "i".to_string()],
+                values: vec![1000],
+                halted: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_interactive() {
+        let mut ascii = Ascii::new(ECHO.parse().unwrap());
+        let mut output = Vec::new();
+        ascii
+            .interactive("north\ntake mug\n".as_bytes(), &mut output)
+            .unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "north\ntake mug\n");
+    }
+}
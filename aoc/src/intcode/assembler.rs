@@ -0,0 +1,221 @@
+use super::{Instruction, IntCodeError, Mode, OpCode, Program, Result};
+use std::collections::HashMap;
+
+/// A parameter or data value before labels are resolved.
+enum Value<'a> {
+    Number(i64),
+    Label(&'a str),
+}
+
+enum Statement<'a> {
+    Instruction(OpCode, Vec<(Mode, Value<'a>)>),
+    Data(Vec<Value<'a>>),
+}
+
+impl Program {
+    /// Assembles a program written in the syntax of [`super::Listing`], one instruction or
+    /// `data` directive per line:
+    ///
+    /// ```text
+    /// loop: in 100           ; comments start with a semicolon
+    ///       jz 100, #end
+    ///       mul 100, #2, @1  ; position, immediate and relative mode
+    ///       out @1
+    ///       jnz #1, #loop
+    /// end:  hlt
+    ///       data 0, loop
+    /// ```
+    ///
+    /// Labels can be used wherever a number can and are replaced by the address of the line
+    /// they are defined on.
+    pub fn assemble(source: &str) -> Result<Self> {
+        let mut labels = HashMap::new();
+        let mut statements = Vec::new();
+        let mut address = 0;
+
+        for (n, line) in source.lines().enumerate() {
+            let error = |message: String| IntCodeError::BadAssembly(n + 1, message);
+
+            let mut line = line.split_once(';').map_or(line, |(code, _)| code).trim();
+            if let Some((label, rest)) = line.split_once(':') {
+                let label = label.trim();
+                if !is_label(label) {
+                    return Err(error(format!("invalid label `{label}`")));
+                }
+                if labels.insert(label, address).is_some() {
+                    return Err(error(format!("duplicate label `{label}`")));
+                }
+                line = rest.trim();
+            }
+            if line.is_empty() {
+                continue;
+            }
+
+            let (mnemonic, operands) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let operands: Vec<&str> = operands
+                .split(',')
+                .map(str::trim)
+                .filter(|o| !o.is_empty())
+                .collect();
+
+            let statement = if mnemonic == "data" {
+                let values = operands
+                    .iter()
+                    .map(|o| value(o).ok_or_else(|| error(format!("invalid value `{o}`"))))
+                    .collect::<Result<Vec<_>>>()?;
+                Statement::Data(values)
+            } else {
+                let op_code: OpCode = mnemonic
+                    .parse()
+                    .map_err(|_| error(format!("unknown mnemonic `{mnemonic}`")))?;
+                if operands.len() != op_code.parameters() {
+                    return Err(error(format!(
+                        "`{op_code}` takes {} parameters",
+                        op_code.parameters()
+                    )));
+                }
+
+                let parameters = operands
+                    .iter()
+                    .map(|o| parameter(o).ok_or_else(|| error(format!("invalid parameter `{o}`"))))
+                    .collect::<Result<Vec<_>>>()?;
+                if let Some(n) = op_code.writes()
+                    && parameters[n - 1].0 == Mode::Immediate
+                {
+                    return Err(error(format!("`{op_code}` cannot write to an immediate")));
+                }
+                Statement::Instruction(op_code, parameters)
+            };
+
+            address += match &statement {
+                Statement::Instruction(_, parameters) => parameters.len() + 1,
+                Statement::Data(values) => values.len(),
+            };
+            statements.push((n + 1, statement));
+        }
+
+        let mut program = Vec::with_capacity(address);
+        for (n, statement) in statements {
+            let resolve = |value: &Value| match value {
+                Value::Number(number) => Ok(*number),
+                Value::Label(label) => labels.get(label).map(|a| *a as i64).ok_or_else(|| {
+                    IntCodeError::BadAssembly(n, format!("unknown label `{label}`"))
+                }),
+            };
+
+            match statement {
+                Statement::Instruction(op_code, parameters) => {
+                    let mut modes = [Mode::Position; 3];
+                    for (mode, (m, _)) in modes.iter_mut().zip(&parameters) {
+                        *mode = *m;
+                    }
+                    program.push(i64::from(Instruction { op_code, modes }));
+                    for (_, value) in &parameters {
+                        program.push(resolve(value)?);
+                    }
+                }
+                Statement::Data(values) => {
+                    for value in &values {
+                        program.push(resolve(value)?);
+                    }
+                }
+            }
+        }
+
+        Ok(Program::new(program))
+    }
+}
+
+fn is_label(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn value(s: &str) -> Option<Value<'_>> {
+    if is_label(s) {
+        Some(Value::Label(s))
+    } else {
+        s.parse().ok().map(Value::Number)
+    }
+}
+
+fn parameter(s: &str) -> Option<(Mode, Value<'_>)> {
+    if let Some(s) = s.strip_prefix('#') {
+        Some((Mode::Immediate, value(s)?))
+    } else if let Some(s) = s.strip_prefix('@') {
+        Some((Mode::Relative, value(s)?))
+    } else {
+        Some((Mode::Position, value(s)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::Listing;
+
+    #[test]
+    fn test_assemble() {
+        let program = Program::assemble(
+            "
+            ; 2019 day 2 example
+                add 9, 10, 3
+                mul 3, 11, 0
+                hlt
+                data 30, 40, 50
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            program.memory,
+            vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        let program = Program::assemble(
+            "
+            loop: in x
+                  jz x, #end
+                  out x
+                  jnz #1, #loop
+            end:  hlt
+            x:    data 0, loop
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            program.memory,
+            vec![3, 11, 1006, 11, 10, 4, 11, 1105, 1, 0, 99, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for source in [
+            "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99",
+            "3,9,8,9,10,9,4,9,99,-1,8",
+            "1002,4,3,4,33,21101,1,2,3,11101,1,2,3,10099",
+        ] {
+            let program: Program = source.parse().unwrap();
+            let listing = Listing::disassemble(&program).to_string();
+            assert_eq!(Program::assemble(&listing).unwrap().memory, program.memory);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let line = |source| match Program::assemble(source) {
+            Err(IntCodeError::BadAssembly(line, _)) => line,
+            other => panic!("expected an assembly error, got {other:?}"),
+        };
+
+        assert_eq!(line("hlt\nfoo 1"), 2);
+        assert_eq!(line("add 1, 2"), 1);
+        assert_eq!(line("in #5"), 1);
+        assert_eq!(line("hlt\njnz #1, #nowhere"), 2);
+        assert_eq!(line("a: hlt\na: hlt"), 2);
+        assert_eq!(line("out $1"), 1);
+    }
+}
@@ -0,0 +1,258 @@
+use super::{CPU, OpCode, Parameter, Result};
+use std::{collections::HashMap, fmt::Display};
+
+/// An instruction about to be executed, as passed to trace callbacks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub pc: i64,
+    pub op_code: OpCode,
+    pub parameters: Vec<Parameter>,
+    pub relative_base: i64,
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:>5}: {}", self.pc, self.op_code)?;
+        for (i, parameter) in self.parameters.iter().enumerate() {
+            write!(f, "{}{parameter}", if i == 0 { " " } else { ", " })?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    Address(i64),
+    OpCode(OpCode),
+}
+
+/// Why the [`Debugger`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The next instruction matches the breakpoint and has not been executed
+    Breakpoint(Breakpoint),
+    /// The last instruction wrote to a watched cell
+    Watchpoint {
+        address: i64,
+        old: i64,
+        new: i64,
+    },
+    NeedsInput,
+    Halted,
+}
+
+type Tracer<'a> = Box<dyn FnMut(&Trace) + 'a>;
+
+/// Runs a [`CPU`] one instruction at a time with tracing, breakpoints, watchpoints and
+/// instruction counters. Output is kept in the CPU, see [`CPU::output`].
+pub struct Debugger<'a> {
+    cpu: CPU,
+    tracers: Vec<Tracer<'a>>,
+    breakpoints: Vec<Breakpoint>,
+    watchpoints: Vec<i64>,
+    executed: u64,
+    counts: HashMap<i64, u64>,
+}
+
+impl<'a> Debugger<'a> {
+    pub fn new(cpu: CPU) -> Self {
+        Self {
+            cpu,
+            tracers: Vec::new(),
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+            executed: 0,
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn cpu(&self) -> &CPU {
+        &self.cpu
+    }
+
+    pub fn cpu_mut(&mut self) -> &mut CPU {
+        &mut self.cpu
+    }
+
+    pub fn into_cpu(self) -> CPU {
+        self.cpu
+    }
+
+    /// Calls `tracer` before every executed instruction.
+    pub fn trace(&mut self, tracer: impl FnMut(&Trace) + 'a) {
+        self.tracers.push(Box::new(tracer));
+    }
+
+    pub fn break_at(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    pub fn clear_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.retain(|b| *b != breakpoint);
+    }
+
+    /// Stops after any instruction writing to `address`, even if the value is unchanged.
+    pub fn watch(&mut self, address: i64) {
+        self.watchpoints.push(address);
+    }
+
+    pub fn unwatch(&mut self, address: i64) {
+        self.watchpoints.retain(|a| *a != address);
+    }
+
+    /// Total number of instructions executed.
+    pub fn executed(&self) -> u64 {
+        self.executed
+    }
+
+    /// Number of times the instruction at `address` was executed.
+    pub fn count(&self, address: i64) -> u64 {
+        self.counts.get(&address).copied().unwrap_or(0)
+    }
+
+    /// Executes a single instruction, ignoring breakpoints. Returns why execution cannot
+    /// continue or which watchpoint the instruction hit, if any.
+    pub fn step(&mut self) -> Result<Option<Stop>> {
+        let pc = self.cpu.pc;
+        let instruction = self.cpu.program.instruction(pc)?;
+
+        match instruction.op_code {
+            OpCode::Halt => return Ok(Some(Stop::Halted)),
+            OpCode::Input if self.cpu.input.is_empty() => return Ok(Some(Stop::NeedsInput)),
+            _ => {}
+        }
+
+        if !self.tracers.is_empty() {
+            let parameters = (1..=instruction.op_code.parameters())
+                .map(|n| {
+                    Ok(Parameter {
+                        mode: instruction.modes[n - 1],
+                        value: self.cpu.program.value(self.cpu.pc_offset(n as i64)?)?,
+                    })
+                })
+                .collect::<Result<_>>()?;
+            let trace = Trace {
+                pc,
+                op_code: instruction.op_code,
+                parameters,
+                relative_base: self.cpu.relative_base,
+            };
+            for tracer in self.tracers.iter_mut() {
+                tracer(&trace);
+            }
+        }
+
+        let written = match instruction.op_code.writes() {
+            Some(n) if !self.watchpoints.is_empty() => {
+                let address = self.cpu.address(instruction, n)?;
+                Some((address, self.cpu.program.value(address)?))
+            }
+            _ => None,
+        };
+
+        self.cpu.step()?;
+        self.executed += 1;
+        *self.counts.entry(pc).or_default() += 1;
+
+        match written {
+            Some((address, old)) if self.watchpoints.contains(&address) => {
+                Ok(Some(Stop::Watchpoint {
+                    address,
+                    old,
+                    new: self.cpu.program.value(address)?,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Runs until a breakpoint or watchpoint is hit, the program halts or it waits for input.
+    /// A breakpoint at the current instruction does not stop it again.
+    pub fn resume(&mut self) -> Result<Stop> {
+        if let Some(stop) = self.step()? {
+            return Ok(stop);
+        }
+
+        loop {
+            if let Some(breakpoint) = self.breakpoint()? {
+                return Ok(Stop::Breakpoint(breakpoint));
+            }
+            if let Some(stop) = self.step()? {
+                return Ok(stop);
+            }
+        }
+    }
+
+    fn breakpoint(&self) -> Result<Option<Breakpoint>> {
+        if self.breakpoints.is_empty() {
+            return Ok(None);
+        }
+
+        let op_code = self.cpu.program.instruction(self.cpu.pc)?.op_code;
+        Ok(self.breakpoints.iter().copied().find(|b| match b {
+            Breakpoint::Address(address) => *address == self.cpu.pc,
+            Breakpoint::OpCode(o) => *o == op_code,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cpu(program: &str) -> CPU {
+        CPU::new(program.parse().unwrap())
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut traces = Vec::new();
+        let mut debugger = Debugger::new(cpu("1,9,10,3,2,3,11,0,99,30,40,50"));
+        debugger.trace(|trace| traces.push(trace.to_string()));
+        assert_eq!(debugger.resume().unwrap(), Stop::Halted);
+        assert_eq!(debugger.executed(), 2);
+        drop(debugger);
+
+        assert_eq!(traces, vec!["    0: add 9, 10, 3", "    4: mul 3, 11, 0"]);
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        // Doubles every input until it reads a zero
+        let mut debugger = Debugger::new(cpu("3,100,1006,100,14,1002,100,2,101,4,101,1105,1,0,99"));
+        debugger.break_at(Breakpoint::OpCode(OpCode::Output));
+        debugger.break_at(Breakpoint::Address(14));
+        for input in [3, 4, 0] {
+            debugger.cpu_mut().push_input(input);
+        }
+
+        let output = Stop::Breakpoint(Breakpoint::OpCode(OpCode::Output));
+        assert_eq!(debugger.resume().unwrap(), output);
+        assert!(debugger.cpu().output().is_empty());
+        assert_eq!(debugger.resume().unwrap(), output);
+        assert_eq!(debugger.cpu().output(), &[6]);
+        assert_eq!(
+            debugger.resume().unwrap(),
+            Stop::Breakpoint(Breakpoint::Address(14))
+        );
+        assert_eq!(debugger.resume().unwrap(), Stop::Halted);
+        assert_eq!(debugger.cpu().output(), &[6, 8]);
+        assert_eq!(debugger.count(0), 3);
+        assert_eq!(debugger.count(9), 2);
+    }
+
+    #[test]
+    fn test_watchpoints() {
+        let mut debugger = Debugger::new(cpu("1,9,10,3,2,3,11,0,99,30,40,50"));
+        debugger.watch(0);
+        assert_eq!(
+            debugger.resume().unwrap(),
+            Stop::Watchpoint {
+                address: 0,
+                old: 1,
+                new: 3500
+            }
+        );
+        assert_eq!(debugger.step().unwrap(), Some(Stop::Halted));
+    }
+}
@@ -0,0 +1,257 @@
+use super::{CPU, Instruction, Mode, OpCode, Program, Result};
+use std::{collections::HashSet, fmt::Display};
+
+/// An instruction parameter, written `12` in position mode, `#12` in immediate mode and
+/// `@12` in relative mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parameter {
+    pub mode: Mode,
+    pub value: i64,
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.mode {
+            Mode::Position => write!(f, "{}", self.value),
+            Mode::Immediate => write!(f, "#{}", self.value),
+            Mode::Relative => write!(f, "@{}", self.value),
+        }
+    }
+}
+
+/// One line of a [`Listing`], e.g. `mul 4, #3, 4` or `data 30, 40, 50`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    Instruction {
+        address: i64,
+        op_code: OpCode,
+        parameters: Vec<Parameter>,
+    },
+    /// Values that were not decoded as instructions
+    Data { address: i64, values: Vec<i64> },
+}
+
+impl Line {
+    pub fn address(&self) -> i64 {
+        match self {
+            Self::Instruction { address, .. } | Self::Data { address, .. } => *address,
+        }
+    }
+
+    /// Number of memory cells the line covers.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Instruction { parameters, .. } => parameters.len() + 1,
+            Self::Data { values, .. } => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (mnemonic, operands): (String, Vec<String>) = match self {
+            Self::Instruction {
+                op_code,
+                parameters,
+                ..
+            } => (
+                op_code.to_string(),
+                parameters.iter().map(|p| p.to_string()).collect(),
+            ),
+            Self::Data { values, .. } => (
+                "data".to_string(),
+                values.iter().map(|v| v.to_string()).collect(),
+            ),
+        };
+
+        if operands.is_empty() {
+            write!(f, "{mnemonic}")
+        } else {
+            write!(f, "{mnemonic} {}", operands.join(", "))
+        }
+    }
+}
+
+/// A disassembled program, displayed one line per instruction with its address as a comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listing(Vec<Line>);
+
+impl Listing {
+    /// Decodes instructions from address 0 onwards. Values that do not decode as a complete
+    /// instruction become data, but data that happens to decode is listed as instructions.
+    pub fn disassemble(program: &Program) -> Self {
+        Self::decode(program, |_| true)
+    }
+
+    /// Runs a copy of `cpu` until it halts, waits for input or has executed `max_steps`
+    /// instructions and decodes only the addresses it executed as instructions, listing
+    /// everything else as data. A program that never halts gives a partial listing.
+    pub fn executed(cpu: &CPU, max_steps: u64) -> Result<Self> {
+        let mut reached = HashSet::new();
+        let mut run = cpu.clone();
+        for _ in 0..max_steps {
+            reached.insert(run.pc);
+            match run.program.instruction(run.pc)?.op_code {
+                OpCode::Halt => break,
+                OpCode::Input if run.input.is_empty() => break,
+                _ => run.step()?,
+            }
+        }
+
+        Ok(Self::decode(&cpu.program, |address| {
+            reached.contains(&address)
+        }))
+    }
+
+    pub fn lines(&self) -> &[Line] {
+        &self.0
+    }
+
+    fn decode(program: &Program, is_code: impl Fn(i64) -> bool) -> Self {
+        let values = &program.memory;
+        let mut lines = Vec::new();
+        let mut address = 0;
+
+        while address < values.len() {
+            let instruction = if is_code(address as i64) {
+                decode_instruction(values, address)
+            } else {
+                None
+            };
+
+            if let Some(line) = instruction {
+                address += line.len();
+                lines.push(line);
+                continue;
+            }
+
+            match lines.last_mut() {
+                Some(Line::Data { values: data, .. }) => data.push(values[address]),
+                _ => lines.push(Line::Data {
+                    address: address as i64,
+                    values: vec![values[address]],
+                }),
+            }
+            address += 1;
+        }
+
+        Self(lines)
+    }
+}
+
+/// Decodes the instruction at `address` if it is complete and would be encoded the same way,
+/// so that assembling the listing gives back the program.
+fn decode_instruction(values: &[i64], address: usize) -> Option<Line> {
+    let instruction = Instruction::try_from(values[address]).ok()?;
+    let count = instruction.op_code.parameters();
+
+    if i64::from(instruction) != values[address]
+        || instruction.modes[count..]
+            .iter()
+            .any(|m| *m != Mode::Position)
+    {
+        return None;
+    }
+    if let Some(n) = instruction.op_code.writes()
+        && instruction.modes[n - 1] == Mode::Immediate
+    {
+        return None;
+    }
+
+    let parameters = values
+        .get(address + 1..address + 1 + count)?
+        .iter()
+        .zip(instruction.modes)
+        .map(|(value, mode)| Parameter {
+            mode,
+            value: *value,
+        })
+        .collect();
+
+    Some(Line::Instruction {
+        address: address as i64,
+        op_code: instruction.op_code,
+        parameters,
+    })
+}
+
+impl Display for Listing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in &self.0 {
+            writeln!(f, "{:<32}; {}", line.to_string(), line.address())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(listing: &Listing) -> Vec<String> {
+        listing.lines().iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let program = "1,9,10,3,2,3,11,0,99,30,40,50".parse().unwrap();
+        assert_eq!(
+            lines(&Listing::disassemble(&program)),
+            vec!["add 9, 10, 3", "mul 3, 11, 0", "hlt", "data 30, 40, 50"]
+        );
+
+        let program = "1002,4,3,4,33,109,-1,21101,1,2,3,204,-1,11101,1,2,3"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            lines(&Listing::disassemble(&program)),
+            vec![
+                "mul 4, #3, 4",
+                "data 33",
+                "arb #-1",
+                "add #1, #2, @3",
+                "out @-1",
+                // Immediate mode write
+                "data 11101, 1, 2, 3"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_executed() {
+        // The data after the halt happens to decode as an instruction
+        let program: Program = "3,9,4,9,99,1,2,3,4,0".parse().unwrap();
+        assert_eq!(
+            lines(&Listing::disassemble(&program)),
+            vec!["in 9", "out 9", "hlt", "add 2, 3, 4", "data 0"]
+        );
+
+        let mut cpu = CPU::new(program);
+        assert_eq!(
+            lines(&Listing::executed(&cpu, 100).unwrap()),
+            vec!["in 9", "data 4, 9, 99, 1, 2, 3, 4, 0"]
+        );
+
+        cpu.push_input(5);
+        let listing = Listing::executed(&cpu, 100).unwrap();
+        assert_eq!(
+            lines(&listing),
+            vec!["in 9", "out 9", "hlt", "data 1, 2, 3, 4, 0"]
+        );
+        assert_eq!(
+            listing.to_string().lines().last().unwrap(),
+            format!("{:<32}; 5", "data 1, 2, 3, 4, 0")
+        );
+
+        // Stops in an endless loop, the instructions after it were never reached
+        let cpu = CPU::new(Program::assemble("loop: jnz #1, #loop\nout #1\nhlt").unwrap());
+        assert_eq!(
+            lines(&Listing::executed(&cpu, 1000).unwrap()),
+            vec!["jnz #1, #0", "data 104, 1, 99"]
+        );
+    }
+}
@@ -0,0 +1,907 @@
+mod ascii;
+mod assembler;
+mod debugger;
+mod disassembler;
+mod network;
+
+pub use ascii::*;
+pub use debugger::*;
+pub use disassembler::*;
+pub use network::*;
+
+use num_enum::TryFromPrimitive;
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+    fmt::Display,
+    str::FromStr,
+};
+
+#[derive(Debug)]
+pub enum IntCodeError {
+    UnknownOpCode,
+    UnknownMode,
+    ImmediateWrite,
+    NoInput,
+    NegativeAddress(i64),
+    /// An address or value does not fit in an `i64`
+    Overflow,
+    BadProgram,
+    /// An assembler error on a line, see [`Program::assemble`]
+    BadAssembly(usize, String),
+    Io(std::io::Error),
+}
+
+impl Display for IntCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownOpCode => write!(f, "UnknownOpCode"),
+            Self::UnknownMode => write!(f, "UnknownMode"),
+            Self::ImmediateWrite => write!(f, "Write to an immediate mode parameter"),
+            Self::NoInput => write!(f, "No input available"),
+            Self::NegativeAddress(addr) => write!(f, "Negative address {addr}"),
+            Self::Overflow => write!(f, "Arithmetic overflow"),
+            Self::BadProgram => write!(f, "BadProgram"),
+            Self::BadAssembly(line, message) => write!(f, "Line {line}: {message}"),
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+        }
+    }
+}
+
+impl Error for IntCodeError {}
+
+impl From<std::io::Error> for IntCodeError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, IntCodeError>;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive, strum::Display, strum::EnumString,
+)]
+#[repr(i64)]
+pub enum OpCode {
+    #[strum(serialize = "add")]
+    Add = 1,
+    #[strum(serialize = "mul")]
+    Mul = 2,
+    #[strum(serialize = "in")]
+    Input = 3,
+    #[strum(serialize = "out")]
+    Output = 4,
+    #[strum(serialize = "jnz")]
+    JumpIfTrue = 5,
+    #[strum(serialize = "jz")]
+    JumpIfFalse = 6,
+    #[strum(serialize = "lt")]
+    LessThan = 7,
+    #[strum(serialize = "eq")]
+    Equals = 8,
+    #[strum(serialize = "arb")]
+    AdjustRelativeBase = 9,
+    #[strum(serialize = "hlt")]
+    Halt = 99,
+}
+
+impl OpCode {
+    /// Number of parameters following the opcode.
+    pub fn parameters(&self) -> usize {
+        match self {
+            Self::Add | Self::Mul | Self::LessThan | Self::Equals => 3,
+            Self::JumpIfTrue | Self::JumpIfFalse => 2,
+            Self::Input | Self::Output | Self::AdjustRelativeBase => 1,
+            Self::Halt => 0,
+        }
+    }
+
+    /// The parameter (1-based) the instruction writes to, if any.
+    pub fn writes(&self) -> Option<usize> {
+        match self {
+            Self::Add | Self::Mul | Self::LessThan | Self::Equals => Some(3),
+            Self::Input => Some(1),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[repr(i64)]
+pub enum Mode {
+    Position = 0,
+    Immediate = 1,
+    Relative = 2,
+}
+
+/// An opcode together with the modes of its parameters, decoded from e.g. `1002`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub op_code: OpCode,
+    pub modes: [Mode; 3],
+}
+
+impl TryFrom<i64> for Instruction {
+    type Error = IntCodeError;
+
+    fn try_from(value: i64) -> Result<Self> {
+        if value < 0 {
+            return Err(IntCodeError::UnknownOpCode);
+        }
+
+        let op_code = OpCode::try_from(value % 100).map_err(|_| IntCodeError::UnknownOpCode)?;
+
+        let mut modes = [Mode::Position; 3];
+        let mut flags = value / 100;
+        for mode in modes.iter_mut() {
+            *mode = Mode::try_from(flags % 10).map_err(|_| IntCodeError::UnknownMode)?;
+            flags /= 10;
+        }
+
+        Ok(Self { op_code, modes })
+    }
+}
+
+impl From<Instruction> for i64 {
+    fn from(instruction: Instruction) -> Self {
+        instruction
+            .modes
+            .iter()
+            .rev()
+            .fold(0, |flags, mode| flags * 10 + *mode as i64)
+            * 100
+            + instruction.op_code as i64
+    }
+}
+
+/// Memory below this address is kept in a `Vec`, writes above it go to a sparse map so a
+/// single far away write does not allocate everything before it.
+const DENSE_LIMIT: usize = 1 << 20;
+
+/// Intcode memory, the program image followed by zeroes. Memory grows when written
+/// beyond the end of the image.
+#[derive(Debug, Clone)]
+pub struct Program {
+    memory: Vec<i64>,
+    sparse: HashMap<usize, i64>,
+}
+
+impl FromStr for Program {
+    type Err = IntCodeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let prog = s
+            .trim()
+            .split(',')
+            .map(|i| i.trim().parse().map_err(|_| IntCodeError::BadProgram))
+            .collect::<std::result::Result<Vec<i64>, IntCodeError>>()?;
+
+        Ok(Program::new(prog))
+    }
+}
+
+impl Program {
+    fn new(memory: Vec<i64>) -> Self {
+        Self {
+            memory,
+            sparse: HashMap::new(),
+        }
+    }
+
+    fn instruction(&self, addr: i64) -> Result<Instruction> {
+        Instruction::try_from(self.value(addr)?)
+    }
+
+    fn value(&self, addr: i64) -> Result<i64> {
+        let addr = usize::try_from(addr).map_err(|_| IntCodeError::NegativeAddress(addr))?;
+        let value = match self.memory.get(addr) {
+            Some(value) => Some(value),
+            None => self.sparse.get(&addr),
+        };
+        Ok(value.copied().unwrap_or(0))
+    }
+
+    fn store(&mut self, addr: i64, value: i64) -> Result<()> {
+        let addr = usize::try_from(addr).map_err(|_| IntCodeError::NegativeAddress(addr))?;
+        if addr < self.memory.len() {
+            self.memory[addr] = value;
+        } else if addr < DENSE_LIMIT {
+            self.memory.resize(addr + 1, 0);
+            self.memory[addr] = value;
+        } else {
+            self.sparse.insert(addr, value);
+        }
+
+        Ok(())
+    }
+}
+
+/// Why [`CPU::run_until_event`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// The next instruction reads input but the input queue is empty
+    NeedsInput,
+    Output(i64),
+    Halted,
+}
+
+/// A copy of a [`CPU`] to return to with [`CPU::restore`].
+#[derive(Debug, Clone)]
+pub struct Snapshot(CPU);
+
+#[derive(Debug)]
+pub struct CPU {
+    program: Program,
+    /// Decoded instructions by address, cleared when the address is written to
+    decoded: Vec<Option<Instruction>>,
+    cache: bool,
+    pc: i64,
+    relative_base: i64,
+    input: VecDeque<i64>,
+    output: Vec<i64>,
+}
+
+impl Clone for CPU {
+    fn clone(&self) -> Self {
+        Self {
+            program: self.program.clone(),
+            decoded: self.decoded.clone(),
+            cache: self.cache,
+            pc: self.pc,
+            relative_base: self.relative_base,
+            input: self.input.clone(),
+            output: self.output.clone(),
+        }
+    }
+
+    /// Reuses the memory already allocated by `self`.
+    fn clone_from(&mut self, source: &Self) {
+        self.program.memory.clone_from(&source.program.memory);
+        self.program.sparse.clone_from(&source.program.sparse);
+        self.decoded.clone_from(&source.decoded);
+        self.cache = source.cache;
+        self.pc = source.pc;
+        self.relative_base = source.relative_base;
+        self.input.clone_from(&source.input);
+        self.output.clone_from(&source.output);
+    }
+}
+
+impl CPU {
+    pub fn new(program: Program) -> Self {
+        Self {
+            decoded: vec![None; program.memory.len()],
+            program,
+            cache: true,
+            pc: 0,
+            relative_base: 0,
+            input: VecDeque::new(),
+            output: Vec::new(),
+        }
+    }
+
+    /// Turns the decoded instruction cache on or off, e.g. to compare the two or to rule
+    /// out the cache when debugging.
+    pub fn set_decode_cache(&mut self, enabled: bool) {
+        self.cache = enabled;
+        self.decoded.fill(None);
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.clone())
+    }
+
+    /// Returns to the state of `snapshot` without allocating when memory has not grown since.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.clone_from(&snapshot.0);
+    }
+
+    pub fn step(&mut self) -> Result<()> {
+        let instruction = self.fetch()?;
+        self.execute(instruction)
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        loop {
+            let instruction = self.fetch()?;
+            if instruction.op_code == OpCode::Halt {
+                return Ok(());
+            }
+            self.execute(instruction)?;
+        }
+    }
+
+    /// Runs until the program halts, outputs a value or waits for input. An output value is
+    /// handed over in the event rather than kept in [`CPU::output`]. Execution continues
+    /// where it stopped on the next call, after pushing input if needed.
+    pub fn run_until_event(&mut self) -> Result<Event> {
+        loop {
+            let instruction = self.fetch()?;
+            match instruction.op_code {
+                OpCode::Halt => return Ok(Event::Halted),
+                OpCode::Input if self.input.is_empty() => return Ok(Event::NeedsInput),
+                OpCode::Output => {
+                    self.execute(instruction)?;
+                    let value = self
+                        .output
+                        .pop()
+                        .expect("output instruction produced a value");
+                    return Ok(Event::Output(value));
+                }
+                _ => self.execute(instruction)?,
+            }
+        }
+    }
+
+    pub fn halted(&self) -> bool {
+        self.program
+            .instruction(self.pc)
+            .is_ok_and(|i| i.op_code == OpCode::Halt)
+    }
+
+    pub fn pos0(&self) -> Result<i64> {
+        self.program.value(0)
+    }
+
+    pub fn read(&self, addr: i64) -> Result<i64> {
+        self.program.value(addr)
+    }
+
+    pub fn write(&mut self, addr: i64, value: i64) -> Result<()> {
+        self.store(addr, value)
+    }
+
+    /// Queues a value for the next input instruction.
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    /// All values output so far.
+    pub fn output(&self) -> &[i64] {
+        &self.output
+    }
+
+    /// Empties the output queue, returning its values in output order.
+    pub fn take_output(&mut self) -> Vec<i64> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Decodes the instruction at the program counter, or takes it from the cache.
+    fn fetch(&mut self) -> Result<Instruction> {
+        let slot = usize::try_from(self.pc)
+            .ok()
+            .and_then(|pc| self.decoded.get_mut(pc));
+
+        match slot {
+            Some(Some(instruction)) => Ok(*instruction),
+            Some(slot) if self.cache => {
+                let instruction = self.program.instruction(self.pc)?;
+                *slot = Some(instruction);
+                Ok(instruction)
+            }
+            _ => self.program.instruction(self.pc),
+        }
+    }
+
+    fn execute(&mut self, instruction: Instruction) -> Result<()> {
+        self.pc = match instruction.op_code {
+            OpCode::Add => self.add(instruction)?,
+            OpCode::Mul => self.mul(instruction)?,
+            OpCode::Input => self.read_input(instruction)?,
+            OpCode::Output => self.write_output(instruction)?,
+            OpCode::JumpIfTrue => self.jump_if(instruction, true)?,
+            OpCode::JumpIfFalse => self.jump_if(instruction, false)?,
+            OpCode::LessThan => self.less_than(instruction)?,
+            OpCode::Equals => self.equals(instruction)?,
+            OpCode::AdjustRelativeBase => self.adjust_relative_base(instruction)?,
+            OpCode::Halt => self.halt()?,
+        };
+
+        Ok(())
+    }
+
+    /// Writes to memory, dropping any cached instruction at the address.
+    fn store(&mut self, addr: i64, value: i64) -> Result<()> {
+        self.program.store(addr, value)?;
+        if let Some(slot) = usize::try_from(addr)
+            .ok()
+            .and_then(|addr| self.decoded.get_mut(addr))
+        {
+            *slot = None;
+        }
+        Ok(())
+    }
+
+    /// Fetches parameter `n` (1-based) of the current instruction.
+    fn param(&self, instruction: Instruction, n: usize) -> Result<i64> {
+        let raw = self.program.value(self.pc_offset(n as i64)?)?;
+
+        match instruction.modes[n - 1] {
+            Mode::Position => self.program.value(raw),
+            Mode::Immediate => Ok(raw),
+            Mode::Relative => self.program.value(self.relative(raw)?),
+        }
+    }
+
+    /// The address parameter `n` (1-based) of the current instruction writes to.
+    fn address(&self, instruction: Instruction, n: usize) -> Result<i64> {
+        let raw = self.program.value(self.pc_offset(n as i64)?)?;
+
+        match instruction.modes[n - 1] {
+            Mode::Position => Ok(raw),
+            Mode::Immediate => Err(IntCodeError::ImmediateWrite),
+            Mode::Relative => self.relative(raw),
+        }
+    }
+
+    /// The address `offset` cells after the program counter.
+    fn pc_offset(&self, offset: i64) -> Result<i64> {
+        self.pc.checked_add(offset).ok_or(IntCodeError::Overflow)
+    }
+
+    fn relative(&self, offset: i64) -> Result<i64> {
+        self.relative_base
+            .checked_add(offset)
+            .ok_or(IntCodeError::Overflow)
+    }
+
+    fn add(&mut self, instruction: Instruction) -> Result<i64> {
+        let operand1 = self.param(instruction, 1)?;
+        let operand2 = self.param(instruction, 2)?;
+        let sum = operand1
+            .checked_add(operand2)
+            .ok_or(IntCodeError::Overflow)?;
+        self.store(self.address(instruction, 3)?, sum)?;
+        self.pc_offset(4)
+    }
+
+    fn mul(&mut self, instruction: Instruction) -> Result<i64> {
+        let operand1 = self.param(instruction, 1)?;
+        let operand2 = self.param(instruction, 2)?;
+        let product = operand1
+            .checked_mul(operand2)
+            .ok_or(IntCodeError::Overflow)?;
+        self.store(self.address(instruction, 3)?, product)?;
+        self.pc_offset(4)
+    }
+
+    fn read_input(&mut self, instruction: Instruction) -> Result<i64> {
+        let value = self.input.pop_front().ok_or(IntCodeError::NoInput)?;
+        self.store(self.address(instruction, 1)?, value)?;
+        self.pc_offset(2)
+    }
+
+    fn write_output(&mut self, instruction: Instruction) -> Result<i64> {
+        let value = self.param(instruction, 1)?;
+        self.output.push(value);
+        self.pc_offset(2)
+    }
+
+    fn jump_if(&mut self, instruction: Instruction, condition: bool) -> Result<i64> {
+        if (self.param(instruction, 1)? != 0) == condition {
+            Ok(self.param(instruction, 2)?)
+        } else {
+            self.pc_offset(3)
+        }
+    }
+
+    fn less_than(&mut self, instruction: Instruction) -> Result<i64> {
+        let result = self.param(instruction, 1)? < self.param(instruction, 2)?;
+        self.store(self.address(instruction, 3)?, result as i64)?;
+        self.pc_offset(4)
+    }
+
+    fn equals(&mut self, instruction: Instruction) -> Result<i64> {
+        let result = self.param(instruction, 1)? == self.param(instruction, 2)?;
+        self.store(self.address(instruction, 3)?, result as i64)?;
+        self.pc_offset(4)
+    }
+
+    fn adjust_relative_base(&mut self, instruction: Instruction) -> Result<i64> {
+        self.relative_base = self.relative(self.param(instruction, 1)?)?;
+        self.pc_offset(2)
+    }
+
+    fn halt(&self) -> Result<i64> {
+        Ok(self.pc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_y19d02_1() {
+        let mut cpu = CPU::new("1,0,0,0,99".parse().unwrap());
+        cpu.run().unwrap();
+        assert_eq!(cpu.program.memory, vec![2, 0, 0, 0, 99]);
+    }
+
+    #[test]
+    fn examples_y19d02_2() {
+        let mut cpu = CPU::new("2,3,0,3,99".parse().unwrap());
+        cpu.run().unwrap();
+        assert_eq!(cpu.program.memory, vec![2, 3, 0, 6, 99]);
+    }
+
+    #[test]
+    fn examples_y19d02_3() {
+        let mut cpu = CPU::new("2,4,4,5,99,0".parse().unwrap());
+        cpu.run().unwrap();
+        assert_eq!(cpu.program.memory, vec![2, 4, 4, 5, 99, 9801]);
+    }
+
+    #[test]
+    fn examples_y19d02_4() {
+        let mut cpu = CPU::new("1,1,1,4,99,5,6,0,99".parse().unwrap());
+        cpu.run().unwrap();
+        assert_eq!(cpu.program.memory, vec![30, 1, 1, 4, 2, 5, 6, 0, 99]);
+    }
+
+    #[test]
+    fn test_instruction() {
+        let instruction = Instruction::try_from(1002).unwrap();
+        assert_eq!(instruction.op_code, OpCode::Mul);
+        assert_eq!(
+            instruction.modes,
+            [Mode::Position, Mode::Immediate, Mode::Position]
+        );
+
+        let instruction = Instruction::try_from(21107).unwrap();
+        assert_eq!(instruction.op_code, OpCode::LessThan);
+        assert_eq!(
+            instruction.modes,
+            [Mode::Immediate, Mode::Immediate, Mode::Relative]
+        );
+
+        assert!(Instruction::try_from(42).is_err());
+        assert!(Instruction::try_from(301).is_err());
+    }
+
+    #[test]
+    fn examples_y19d05_1() {
+        let mut cpu = CPU::new("1002,4,3,4,33".parse().unwrap());
+        cpu.run().unwrap();
+        assert_eq!(cpu.program.memory, vec![1002, 4, 3, 4, 99]);
+
+        let mut cpu = CPU::new("3,0,4,0,99".parse().unwrap());
+        cpu.push_input(42);
+        cpu.run().unwrap();
+        assert_eq!(cpu.output(), &[42]);
+    }
+
+    #[test]
+    fn examples_y19d05_2() {
+        let equal_to_8 = |input| {
+            let mut cpu = CPU::new("3,9,8,9,10,9,4,9,99,-1,8".parse().unwrap());
+            cpu.push_input(input);
+            cpu.run().unwrap();
+            cpu.output()[0]
+        };
+        assert_eq!(equal_to_8(8), 1);
+        assert_eq!(equal_to_8(7), 0);
+
+        let less_than_8 = |input| {
+            let mut cpu = CPU::new("3,3,1107,0,8,3,4,3,99".parse().unwrap());
+            cpu.push_input(input);
+            cpu.run().unwrap();
+            cpu.output()[0]
+        };
+        assert_eq!(less_than_8(7), 1);
+        assert_eq!(less_than_8(8), 0);
+
+        let compare_to_8 = |input| {
+            let mut cpu = CPU::new(
+                "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,\
+                 1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,\
+                 999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99"
+                    .parse()
+                    .unwrap(),
+            );
+            cpu.push_input(input);
+            cpu.run().unwrap();
+            cpu.output()[0]
+        };
+        assert_eq!(compare_to_8(7), 999);
+        assert_eq!(compare_to_8(8), 1000);
+        assert_eq!(compare_to_8(9), 1001);
+    }
+
+    #[test]
+    fn examples_y19d05_jumps() {
+        let is_nonzero = |program: &str, input| {
+            let mut cpu = CPU::new(program.parse().unwrap());
+            cpu.push_input(input);
+            cpu.run().unwrap();
+            cpu.output()[0]
+        };
+
+        let position = "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9";
+        assert_eq!(is_nonzero(position, 0), 0);
+        assert_eq!(is_nonzero(position, 5), 1);
+
+        let immediate = "3,3,1105,-1,9,1101,0,0,12,4,12,99,1";
+        assert_eq!(is_nonzero(immediate, 0), 0);
+        assert_eq!(is_nonzero(immediate, 5), 1);
+    }
+
+    #[test]
+    fn examples_y19d09() {
+        let quine = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        let mut cpu = CPU::new(quine.parse().unwrap());
+        cpu.run().unwrap();
+        let output: Vec<String> = cpu.output().iter().map(|v| v.to_string()).collect();
+        assert_eq!(output.join(","), quine);
+
+        let mut cpu = CPU::new("1102,34915192,34915192,7,4,7,99,0".parse().unwrap());
+        cpu.run().unwrap();
+        assert_eq!(cpu.output()[0].to_string().len(), 16);
+
+        let mut cpu = CPU::new("104,1125899906842624,99".parse().unwrap());
+        cpu.run().unwrap();
+        assert_eq!(cpu.output(), &[1125899906842624]);
+    }
+
+    #[test]
+    fn examples_y19d07() {
+        // Five amplifiers in a feedback loop, each fed its phase setting first
+        let program: Program = "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,\
+                                -1,28,1005,28,6,99,0,0,5"
+            .parse()
+            .unwrap();
+        let mut amplifiers: Vec<CPU> = [9, 8, 7, 6, 5]
+            .into_iter()
+            .map(|phase| {
+                let mut cpu = CPU::new(program.clone());
+                cpu.push_input(phase);
+                cpu
+            })
+            .collect();
+
+        let mut signal = 0;
+        'feedback: loop {
+            for amplifier in amplifiers.iter_mut() {
+                amplifier.push_input(signal);
+                match amplifier.run_until_event().unwrap() {
+                    Event::Output(value) => signal = value,
+                    Event::Halted => break 'feedback,
+                    Event::NeedsInput => panic!("amplifier waiting without output"),
+                }
+            }
+        }
+        assert_eq!(signal, 139629729);
+    }
+
+    fn assembled(source: &str) -> CPU {
+        CPU::new(Program::assemble(source).unwrap())
+    }
+
+    fn outputs(source: &str, input: &[i64]) -> Vec<i64> {
+        let mut cpu = assembled(source);
+        for value in input {
+            cpu.push_input(*value);
+        }
+        cpu.run().unwrap();
+        cpu.take_output()
+    }
+
+    #[test]
+    fn test_parse() {
+        let program: Program = "1, -2,3\n".parse().unwrap();
+        assert_eq!(program.memory, vec![1, -2, 3]);
+        assert!(matches!(
+            "1,,2".parse::<Program>(),
+            Err(IntCodeError::BadProgram)
+        ));
+    }
+
+    #[test]
+    fn test_add_mul() {
+        let source = "
+                add a, #-5, x
+                mul x, b, x
+                out x
+                hlt
+            a:  data 7
+            b:  data 3
+            x:  data 0
+        ";
+        assert_eq!(outputs(source, &[]), vec![6]);
+    }
+
+    #[test]
+    fn test_io() {
+        let source = "
+                in x
+                in y
+                out y
+                out x
+                out #42
+                hlt
+            x:  data 0
+            y:  data 0
+        ";
+        assert_eq!(outputs(source, &[1, 2]), vec![2, 1, 42]);
+    }
+
+    #[test]
+    fn test_jumps() {
+        let source = "
+                in x
+                jnz x, #nonzero
+                out #0
+                jz #0, #end
+            nonzero:
+                out #1
+            end:
+                hlt
+            x:  data 0
+        ";
+        assert_eq!(outputs(source, &[0]), vec![0]);
+        assert_eq!(outputs(source, &[-3]), vec![1]);
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let source = "
+                in x
+                lt x, #8, result
+                out result
+                eq #8, x, result
+                out result
+                hlt
+            x:  data 0
+            result:
+                data 0
+        ";
+        assert_eq!(outputs(source, &[7]), vec![1, 0]);
+        assert_eq!(outputs(source, &[8]), vec![0, 1]);
+        assert_eq!(outputs(source, &[9]), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_relative_base() {
+        let source = "
+                arb #values
+                out @1
+                arb #2
+                out @0
+                in @-1
+                out @-1
+                hlt
+            values:
+                data 6, 7, 8
+        ";
+        assert_eq!(outputs(source, &[9]), vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn test_run_until_event() {
+        let mut cpu = assembled(
+            "
+            loop: in x
+                  jz x, #end
+                  mul x, #2, x
+                  out x
+                  jnz #1, #loop
+            end:  hlt
+            x:    data 0
+            ",
+        );
+        assert_eq!(cpu.run_until_event().unwrap(), Event::NeedsInput);
+        cpu.push_input(21);
+        assert_eq!(cpu.run_until_event().unwrap(), Event::Output(42));
+        assert_eq!(cpu.run_until_event().unwrap(), Event::NeedsInput);
+        cpu.push_input(5);
+        cpu.push_input(0);
+        assert_eq!(cpu.run_until_event().unwrap(), Event::Output(10));
+        assert_eq!(cpu.run_until_event().unwrap(), Event::Halted);
+        assert!(cpu.output().is_empty());
+    }
+
+    #[test]
+    fn test_memory() {
+        // Reads past the program are zero, writes grow memory
+        let mut cpu = assembled(
+            "
+                add #-3, #5, 1000
+                out 2000
+                hlt
+            ",
+        );
+        cpu.run().unwrap();
+        assert_eq!(cpu.output(), &[0]);
+        assert_eq!(cpu.read(1000).unwrap(), 2);
+
+        let mut cpu = assembled("out -1");
+        assert!(matches!(cpu.run(), Err(IntCodeError::NegativeAddress(-1))));
+
+        // Runs into the zeroes after the program
+        let mut cpu = assembled("add 0, 0, 0");
+        assert!(matches!(cpu.run(), Err(IntCodeError::UnknownOpCode)));
+
+        // Far away addresses do not allocate the memory before them
+        let mut cpu = assembled(
+            "
+                add #7, #0, 1000000000000000
+                out 1000000000000000
+                out 999999999999999
+                hlt
+            ",
+        );
+        cpu.run().unwrap();
+        assert_eq!(cpu.output(), &[7, 0]);
+        assert!(cpu.program.memory.len() <= DENSE_LIMIT);
+    }
+
+    #[test]
+    fn test_overflow() {
+        for source in [
+            "add #9223372036854775807, #1, 0",
+            "mul #4611686018427387904, #2, 0",
+            "arb #9223372036854775807\narb #1",
+            "arb #9223372036854775807\nout @1",
+        ] {
+            let mut cpu = assembled(source);
+            assert!(matches!(cpu.run(), Err(IntCodeError::Overflow)), "{source}");
+        }
+
+        // Jumps to an add written next to the last address, its second operand overflows
+        let mut cpu = assembled("jnz #1, #9223372036854775806");
+        cpu.write(i64::MAX - 1, 1).unwrap();
+        assert!(matches!(cpu.run(), Err(IntCodeError::Overflow)));
+    }
+
+    #[test]
+    fn test_self_modifying() {
+        // Replaces the already executed first instruction with a halt
+        let source = "
+            start: out #7
+                   add #99, #0, start
+                   jz #0, #start
+        ";
+        for cache in [true, false] {
+            let mut cpu = assembled(source);
+            cpu.set_decode_cache(cache);
+            assert_eq!(cpu.run_until_event().unwrap(), Event::Output(7));
+            assert_eq!(cpu.run_until_event().unwrap(), Event::Halted);
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut cpu = assembled(
+            "
+                in 1000
+                out 1000
+                hlt
+            ",
+        );
+        let snapshot = cpu.snapshot();
+
+        cpu.push_input(1);
+        cpu.run().unwrap();
+        assert_eq!(cpu.read(1000).unwrap(), 1);
+
+        cpu.restore(&snapshot);
+        assert_eq!(cpu.read(1000).unwrap(), 0);
+        assert!(cpu.output().is_empty());
+        cpu.push_input(2);
+        cpu.run().unwrap();
+        assert_eq!(cpu.output(), &[2]);
+    }
+
+    #[test]
+    fn test_immediate_write() {
+        let mut cpu = CPU::new("1101,1,2,3,99".parse().unwrap());
+        cpu.write(0, 11101).unwrap();
+        assert!(matches!(cpu.run(), Err(IntCodeError::ImmediateWrite)));
+    }
+
+    #[test]
+    fn test_no_input() {
+        let mut cpu = assembled("in 0");
+        assert!(matches!(cpu.run(), Err(IntCodeError::NoInput)));
+    }
+}
@@ -0,0 +1,238 @@
+use super::{CPU, Event, Program, Result};
+use std::collections::{BTreeMap, VecDeque};
+
+/// A packet of two values sent over a [`Network`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packet {
+    pub source: i64,
+    pub destination: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A network node implemented in Rust rather than Intcode, e.g. the NAT of 2019 day 23.
+pub trait Router {
+    /// Handles a packet sent to the router's address.
+    fn receive(&mut self, packet: Packet);
+
+    /// Called once per round while the whole network is idle, returns packets to send.
+    fn idle(&mut self) -> Vec<Packet> {
+        Vec::new()
+    }
+}
+
+struct Machine {
+    cpu: CPU,
+    inbox: VecDeque<Packet>,
+    output: Vec<i64>,
+    booted: bool,
+    idle: bool,
+    halted: bool,
+}
+
+/// Intcode machines at addresses `0..size` that send each other packets as `destination, x, y`
+/// output triples. A machine reads its address first, then the `x, y` of each received packet
+/// or `-1` when it has none.
+///
+/// Machines take turns in address order, so a network behaves the same on every run.
+pub struct Network {
+    machines: Vec<Machine>,
+    routers: BTreeMap<i64, Box<dyn Router>>,
+}
+
+impl Network {
+    pub fn new(program: &Program, size: usize) -> Self {
+        let machines = (0..size)
+            .map(|address| {
+                let mut cpu = CPU::new(program.clone());
+                cpu.push_input(address as i64);
+                Machine {
+                    cpu,
+                    inbox: VecDeque::new(),
+                    output: Vec::new(),
+                    booted: false,
+                    idle: false,
+                    halted: false,
+                }
+            })
+            .collect();
+
+        Self {
+            machines,
+            routers: BTreeMap::new(),
+        }
+    }
+
+    /// Lets `router` handle packets sent to `address`, replacing any machine at that address.
+    pub fn install(&mut self, address: i64, router: impl Router + 'static) {
+        self.routers.insert(address, Box::new(router));
+    }
+
+    /// Queues a packet for delivery as if it was sent by `packet.source`.
+    pub fn send(&mut self, packet: Packet) {
+        self.route(packet);
+    }
+
+    /// Whether every machine polled an empty inbox in the last round without sending anything.
+    pub fn is_idle(&self) -> bool {
+        self.machines
+            .iter()
+            .all(|m| m.halted || (m.idle && m.inbox.is_empty()))
+    }
+
+    /// Gives every machine one turn, running it until it waits for input, and returns the
+    /// packets sent during the round. Routers are asked for packets if the round left the
+    /// network idle. Packets to addresses without a machine or router are dropped.
+    pub fn step(&mut self) -> Result<Vec<Packet>> {
+        let mut sent = Vec::new();
+
+        for address in 0..self.machines.len() {
+            let packets = self.turn(address)?;
+            for packet in packets {
+                self.route(packet);
+                sent.push(packet);
+            }
+        }
+
+        if self.is_idle() {
+            let packets: Vec<Packet> = self
+                .routers
+                .values_mut()
+                .flat_map(|router| router.idle())
+                .collect();
+            for packet in packets {
+                self.route(packet);
+                sent.push(packet);
+            }
+        }
+
+        Ok(sent)
+    }
+
+    fn turn(&mut self, address: usize) -> Result<Vec<Packet>> {
+        let machine = &mut self.machines[address];
+        if machine.halted || self.routers.contains_key(&(address as i64)) {
+            machine.idle = true;
+            return Ok(Vec::new());
+        }
+
+        let polled = machine.booted && machine.inbox.is_empty();
+        if machine.inbox.is_empty() {
+            machine.cpu.push_input(-1);
+        }
+        for packet in machine.inbox.drain(..) {
+            machine.cpu.push_input(packet.x);
+            machine.cpu.push_input(packet.y);
+        }
+        machine.booted = true;
+
+        let mut packets = Vec::new();
+        loop {
+            match machine.cpu.run_until_event()? {
+                Event::Output(value) => {
+                    machine.output.push(value);
+                    if let [destination, x, y] = machine.output[..] {
+                        packets.push(Packet {
+                            source: address as i64,
+                            destination,
+                            x,
+                            y,
+                        });
+                        machine.output.clear();
+                    }
+                }
+                Event::NeedsInput => break,
+                Event::Halted => {
+                    machine.halted = true;
+                    break;
+                }
+            }
+        }
+
+        machine.idle = polled && packets.is_empty();
+        Ok(packets)
+    }
+
+    fn route(&mut self, packet: Packet) {
+        if let Some(router) = self.routers.get_mut(&packet.destination) {
+            router.receive(packet);
+        } else if let Some(machine) = usize::try_from(packet.destination)
+            .ok()
+            .and_then(|address| self.machines.get_mut(address))
+        {
+            machine.inbox.push_back(packet);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads its address, then forwards every packet to the next address with `y + 1`.
+    const RELAY: &str = "3,100,1001,100,1,101,3,102,1008,102,-1,103,1005,103,6,3,104,\
+                         1001,104,1,104,4,101,4,102,4,104,1105,1,6";
+
+    fn packet(source: i64, destination: i64, x: i64, y: i64) -> Packet {
+        Packet {
+            source,
+            destination,
+            x,
+            y,
+        }
+    }
+
+    #[test]
+    fn test_relay() {
+        let mut network = Network::new(&RELAY.parse().unwrap(), 3);
+        network.send(packet(-1, 0, 7, 0));
+
+        let mut sent = Vec::new();
+        while !network.is_idle() {
+            sent.extend(network.step().unwrap());
+        }
+        assert_eq!(
+            sent,
+            vec![packet(0, 1, 7, 1), packet(1, 2, 7, 2), packet(2, 3, 7, 3)]
+        );
+    }
+
+    /// Remembers the last packet and sends it to address 0 when the network is idle.
+    struct Nat(Option<Packet>);
+
+    impl Router for Nat {
+        fn receive(&mut self, packet: Packet) {
+            self.0 = Some(packet);
+        }
+
+        fn idle(&mut self) -> Vec<Packet> {
+            self.0
+                .take()
+                .map(|p| packet(3, 0, p.x, p.y))
+                .into_iter()
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_router() {
+        let mut network = Network::new(&RELAY.parse().unwrap(), 3);
+        network.install(3, Nat(None));
+        network.send(packet(-1, 0, 7, 0));
+
+        let mut woken = Vec::new();
+        while woken.len() < 3 {
+            woken.extend(
+                network
+                    .step()
+                    .unwrap()
+                    .into_iter()
+                    .filter(|p| p.source == 3),
+            );
+        }
+        assert_eq!(
+            woken,
+            vec![packet(3, 0, 7, 3), packet(3, 0, 7, 6), packet(3, 0, 7, 9)]
+        );
+    }
+}
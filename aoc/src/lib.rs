@@ -1,10 +1,12 @@
-pub(crate) mod cache;
+pub mod answers;
+pub mod cache;
 mod error;
 mod input;
 pub mod intcode;
 pub mod problem;
 mod solution;
 pub mod utils;
+pub mod vm;
 
 pub use error::*;
 pub use input::*;
@@ -2,9 +2,19 @@ use crate::{cache::Cache, error::*};
 use std::{
     env,
     fmt::{Debug, Display},
+    ops::RangeInclusive,
     time::{Duration, Instant},
 };
 
+/// Environment variable selecting the output format of the solutions, set it to `machine`
+/// to get one `part<N> <seconds> <answer>` line per solved part instead of the report.
+pub const OUTPUT_ENV: &str = "AOC_OUTPUT";
+
+pub(crate) fn machine_output() -> bool {
+    env::var(OUTPUT_ENV).is_ok_and(|format| format == "machine")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Date {
     year: u16,
     day: u8,
@@ -16,25 +26,75 @@ impl Display for Date {
     }
 }
 
+/// The number of problems in each Advent of Code event, the single definition of which
+/// dates are valid. Generated from `aoc/events.txt`, where `cargo xtask new-year` adds new
+/// events.
+pub const EVENTS: &[(RangeInclusive<u16>, u8)] = include!(concat!(env!("OUT_DIR"), "/events.rs"));
+
+/// The number of problems in the event of `year`, `None` if there is no such event.
+pub fn days_in_year(year: u16) -> Option<u8> {
+    EVENTS
+        .iter()
+        .find(|(years, _)| years.contains(&year))
+        .map(|(_, days)| *days)
+}
+
 impl Date {
     pub fn new(year: u16, day: u8) -> Result<Self> {
-        match (year, day) {
-            (2025, 1..=12) => Ok(Self { year, day }),
-            (2015..=2024, 1..=25) => Ok(Self { year, day }),
+        match days_in_year(year) {
+            Some(days) if (1..=days).contains(&day) => Ok(Self { year, day }),
             _ => Err(AoCError::InvalidDay(year, day)),
         }
     }
 
+    /// All dates of the event in `year`, empty if there is no such event.
+    pub fn all(year: u16) -> impl Iterator<Item = Self> {
+        (1..=days_in_year(year).unwrap_or(0)).map(move |day| Self { year, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
     pub fn filename(&self) -> String {
         format!("y{:02}d{:02}.txt", self.year % 2000, self.day)
     }
 
+    pub fn puzzle_filename(&self) -> String {
+        format!("y{:02}d{:02}.html", self.year % 2000, self.day)
+    }
+
+    /// The inverse of `filename`, `None` if the name is not a valid input file name.
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let (year, day) = filename
+            .strip_prefix('y')?
+            .strip_suffix(".txt")?
+            .split_once('d')?;
+
+        Self::new(2000 + year.parse::<u16>().ok()?, day.parse().ok()?).ok()
+    }
+
     pub fn url(&self) -> String {
         format!(
             "https://adventofcode.com/{}/day/{}/input",
             self.year, self.day
         )
     }
+
+    pub fn puzzle_url(&self) -> String {
+        format!("https://adventofcode.com/{}/day/{}", self.year, self.day)
+    }
+
+    pub fn answer_url(&self) -> String {
+        format!(
+            "https://adventofcode.com/{}/day/{}/answer",
+            self.year, self.day
+        )
+    }
 }
 
 pub enum ParseResult {
@@ -90,6 +150,16 @@ pub struct Solution<T: Display, U: Display> {
 
 impl<T: Display, U: Display> Display for Solution<T, U> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if machine_output() {
+            if let SolveResult::Solved(res, dur) = &self.task1 {
+                writeln!(f, "part1 {} {res}", dur.as_secs_f64())?;
+            }
+            if let SolveResult::Solved(res, dur) = &self.task2 {
+                writeln!(f, "part2 {} {res}", dur.as_secs_f64())?;
+            }
+            return Ok(());
+        }
+
         writeln!(f, "Advent of Code {} solution", self.date)?;
         writeln!(f, "--------------------------------")?;
         writeln!(f, "Parse: {}", self.parse)?;
@@ -156,3 +226,27 @@ where
         assert_eq!(result, expected);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date() {
+        let date = Date::new(2024, 6).unwrap();
+        assert_eq!(date.filename(), "y24d06.txt");
+        assert_eq!(Date::from_filename("y24d06.txt"), Some(date));
+        assert_eq!(Date::from_filename("y15d25.txt"), Date::new(2015, 25).ok());
+
+        assert_eq!(Date::from_filename("y24d06.html"), None);
+        assert_eq!(Date::from_filename("y25d13.txt"), None);
+        assert_eq!(Date::from_filename("y14d01.txt"), None);
+        assert_eq!(Date::from_filename("24d06.txt"), None);
+        assert_eq!(Date::from_filename("yxxd06.txt"), None);
+        assert_eq!(Date::from_filename("answers.txt"), None);
+
+        assert!(Date::new(2025, 12).is_ok());
+        assert!(Date::new(2025, 0).is_err());
+        assert_eq!(Date::all(2014).count(), 0);
+    }
+}
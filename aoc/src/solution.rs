@@ -1,5 +1,6 @@
 use crate::AoCInput;
 use crate::error::*;
+use crate::problem::machine_output;
 use std::{
     fmt::Display,
     time::{Duration, Instant},
@@ -111,6 +112,16 @@ where
     T: Copy,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if machine_output() {
+            if let SolutionStatus::Solved(time, solution) = &self.task1 {
+                writeln!(f, "part1 {} {solution}", time.as_secs_f64())?;
+            }
+            if let SolutionStatus::Solved(time, solution) = &self.task2 {
+                writeln!(f, "part2 {} {solution}", time.as_secs_f64())?;
+            }
+            return Ok(());
+        }
+
         writeln!(f, "{}", self.parse)?;
         writeln!(f, "Task 1: {}", self.task1)?;
         writeln!(f, "Task 2: {}", self.task2)
@@ -0,0 +1,434 @@
+use super::{Direction, Point, PointN, PointSet, SparseGrid, Vec2d};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+/// Which cells around a cell are its neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// North, east, south and west
+    VonNeumann,
+    /// All eight surrounding cells
+    Moore,
+}
+
+impl Neighbourhood {
+    pub fn directions(&self) -> &'static [Direction] {
+        match self {
+            Self::VonNeumann => &Direction::CARDINAL,
+            Self::Moore => &Direction::ALL,
+        }
+    }
+}
+
+/// A repeating sequence of generations, found by [`Automaton::find_cycle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// First generation of the cycle
+    pub start: u64,
+    pub length: u64,
+}
+
+/// A rule applied to every cell at the same time, one generation after the other.
+pub trait Automaton {
+    fn step(&mut self);
+
+    /// Number of steps taken so far.
+    fn generation(&self) -> u64;
+
+    /// A hash of the cells, equal for equal states.
+    fn state_hash(&self) -> u64;
+
+    fn run(&mut self, generations: u64) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Steps until a state repeats or `limit` generations are reached. States are compared by
+    /// [`Automaton::state_hash`], so a hash collision could report a false cycle.
+    fn find_cycle(&mut self, limit: u64) -> Option<Cycle> {
+        let mut seen = HashMap::new();
+        while self.generation() < limit {
+            if let Some(start) = seen.insert(self.state_hash(), self.generation()) {
+                return Some(Cycle {
+                    start,
+                    length: self.generation() - start,
+                });
+            }
+            self.step();
+        }
+        None
+    }
+}
+
+/// Steps `automaton` to `generation`, skipping whole cycles once one is found. Returns the
+/// number of generations skipped.
+fn fast_forward(automaton: &mut impl Automaton, generation: u64) -> (Option<Cycle>, u64) {
+    let cycle = automaton.find_cycle(generation);
+    let remaining = generation.saturating_sub(automaton.generation());
+    let Some(cycle) = cycle else {
+        return (None, 0);
+    };
+
+    automaton.run(remaining % cycle.length);
+    (Some(cycle), remaining - remaining % cycle.length)
+}
+
+/// A cellular automaton on a [`Vec2d`], the cells outside it have no state. The `rule` gets
+/// a cell and its neighbours inside the grid and returns the cell's next state.
+pub struct GridAutomaton<T, R> {
+    current: Vec2d<T>,
+    next: Vec2d<T>,
+    neighbourhood: Neighbourhood,
+    rule: R,
+    generation: u64,
+}
+
+impl<T, R> GridAutomaton<T, R>
+where
+    T: Clone + Hash,
+    R: Fn(&T, &[&T]) -> T,
+{
+    pub fn new(grid: Vec2d<T>, neighbourhood: Neighbourhood, rule: R) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            neighbourhood,
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Vec2d<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Vec2d<T> {
+        self.current
+    }
+
+    /// Steps to `generation`, e.g. 1,000,000,000, skipping ahead once the states repeat.
+    /// Returns the cycle if one was found on the way.
+    pub fn fast_forward(&mut self, generation: u64) -> Option<Cycle> {
+        let (cycle, skipped) = fast_forward(self, generation);
+        self.generation += skipped;
+        cycle
+    }
+}
+
+impl<T, R> Automaton for GridAutomaton<T, R>
+where
+    T: Clone + Hash,
+    R: Fn(&T, &[&T]) -> T,
+{
+    fn step(&mut self) {
+        let size = *self.current.size();
+        let mut neighbours = Vec::with_capacity(8);
+
+        for pos in size.iter() {
+            neighbours.clear();
+            neighbours.extend(
+                self.neighbourhood
+                    .directions()
+                    .iter()
+                    .filter_map(|direction| pos.step(*direction, &size))
+                    .map(|pos| &self.current[pos]),
+            );
+            self.next[pos] = (self.rule)(&self.current[pos], &neighbours);
+        }
+
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+    }
+
+    fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.current.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// A cellular automaton on an unbounded [`SparseGrid`]. The `rule` gets a cell, or `None` if
+/// it is not set, and its set neighbours, and returns the cell's next state. Only set cells
+/// and their neighbours are visited, so an unset cell with no set neighbours stays unset.
+pub struct SparseAutomaton<T, R> {
+    current: SparseGrid<T>,
+    next: SparseGrid<T>,
+    neighbourhood: Neighbourhood,
+    rule: R,
+    generation: u64,
+}
+
+impl<T, R> SparseAutomaton<T, R>
+where
+    T: Hash,
+    R: Fn(Option<&T>, &[&T]) -> Option<T>,
+{
+    pub fn new(grid: SparseGrid<T>, neighbourhood: Neighbourhood, rule: R) -> Self {
+        Self {
+            current: grid,
+            next: SparseGrid::new(),
+            neighbourhood,
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &SparseGrid<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> SparseGrid<T> {
+        self.current
+    }
+
+    /// Like [`GridAutomaton::fast_forward`]. A pattern that moves never repeats, so it is
+    /// stepped all the way.
+    pub fn fast_forward(&mut self, generation: u64) -> Option<Cycle> {
+        let (cycle, skipped) = fast_forward(self, generation);
+        self.generation += skipped;
+        cycle
+    }
+}
+
+impl<T, R> Automaton for SparseAutomaton<T, R>
+where
+    T: Hash,
+    R: Fn(Option<&T>, &[&T]) -> Option<T>,
+{
+    fn step(&mut self) {
+        let directions = self.neighbourhood.directions();
+        let candidates: HashSet<Point> = self
+            .current
+            .cells()
+            .flat_map(|(point, _)| {
+                directions
+                    .iter()
+                    .map(move |direction| *point + *direction)
+                    .chain([*point])
+            })
+            .collect();
+
+        let mut neighbours = Vec::with_capacity(8);
+        self.next.clear();
+        for point in candidates {
+            neighbours.clear();
+            neighbours.extend(
+                directions
+                    .iter()
+                    .filter_map(|direction| self.current.get(point + *direction)),
+            );
+            if let Some(item) = (self.rule)(self.current.get(point), &neighbours) {
+                self.next.insert(point, item);
+            }
+        }
+
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+    }
+
+    fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for cell in self.current.iter() {
+            cell.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+/// A cellular automaton on an unbounded [`PointSet`] in `N` dimensions, e.g. the cubes of 2020
+/// day 17. The `rule` gets whether a point is in the set and how many of its
+/// [`PointN::neighbours`] are, and returns whether it is in the next generation.
+pub struct PointAutomaton<const N: usize, R> {
+    points: PointSet<N>,
+    rule: R,
+    generation: u64,
+}
+
+impl<const N: usize, R> PointAutomaton<N, R>
+where
+    R: Fn(bool, usize) -> bool,
+{
+    pub fn new(points: PointSet<N>, rule: R) -> Self {
+        Self {
+            points,
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn points(&self) -> &PointSet<N> {
+        &self.points
+    }
+
+    pub fn into_points(self) -> PointSet<N> {
+        self.points
+    }
+
+    /// Like [`SparseAutomaton::fast_forward`].
+    pub fn fast_forward(&mut self, generation: u64) -> Option<Cycle> {
+        let (cycle, skipped) = fast_forward(self, generation);
+        self.generation += skipped;
+        cycle
+    }
+}
+
+impl<const N: usize, R> Automaton for PointAutomaton<N, R>
+where
+    R: Fn(bool, usize) -> bool,
+{
+    fn step(&mut self) {
+        let mut counts: HashMap<PointN<N>, usize> = HashMap::new();
+        for point in self.points.iter() {
+            counts.entry(*point).or_default();
+            for neighbour in point.neighbours() {
+                *counts.entry(neighbour).or_default() += 1;
+            }
+        }
+
+        self.points = counts
+            .into_iter()
+            .filter(|(point, count)| (self.rule)(self.points.contains(point), *count))
+            .map(|(point, _)| point)
+            .collect();
+        self.generation += 1;
+    }
+
+    fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut points: Vec<_> = self.points.iter().collect();
+        points.sort_unstable();
+
+        let mut hasher = DefaultHasher::new();
+        points.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(alive: &bool, neighbours: &[&bool]) -> bool {
+        let count = neighbours.iter().filter(|n| ***n).count();
+        count == 3 || (*alive && count == 2)
+    }
+
+    fn sparse_life(alive: Option<&()>, neighbours: &[&()]) -> Option<()> {
+        (neighbours.len() == 3 || (alive.is_some() && neighbours.len() == 2)).then_some(())
+    }
+
+    #[test]
+    fn test_grid_automaton() {
+        // 2015 day 18
+        let lights = Vec2d::from_rows(
+            &[".#.#.#", "...##.", "#....#", "..#...", "#.#..#", "####.."],
+            |c| c == '#',
+        );
+        let mut automaton = GridAutomaton::new(lights, Neighbourhood::Moore, life);
+
+        automaton.step();
+        assert_eq!(
+            automaton.grid(),
+            &Vec2d::from_rows(
+                &["..##..", "..##.#", "...##.", "......", "#.....", "#.##.."],
+                |c| c == '#'
+            )
+        );
+        automaton.run(3);
+        assert_eq!(automaton.generation(), 4);
+        assert_eq!(automaton.grid().iter().filter(|(_, on)| **on).count(), 4);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let blinker =
+            Vec2d::from_rows(&[".....", "..#..", "..#..", "..#..", "....."], |c| c == '#');
+        let mut automaton = GridAutomaton::new(blinker.clone(), Neighbourhood::Moore, life);
+
+        let cycle = automaton.fast_forward(1_000_000_001);
+        assert_eq!(
+            cycle,
+            Some(Cycle {
+                start: 0,
+                length: 2
+            })
+        );
+        assert_eq!(automaton.generation(), 1_000_000_001);
+        assert_eq!(automaton.grid().row(2), &[false, true, true, true, false]);
+
+        // Dies out after a generation and stays empty
+        let mut automaton = GridAutomaton::new(
+            Vec2d::from_rows(&["#.", ".."], |c| c == '#'),
+            Neighbourhood::Moore,
+            life,
+        );
+        assert_eq!(
+            automaton.fast_forward(1_000_000_000),
+            Some(Cycle {
+                start: 1,
+                length: 1
+            })
+        );
+        assert_eq!(
+            automaton.grid(),
+            &Vec2d::from_rows(&["..", ".."], |c| c == '#')
+        );
+
+        // No cycle before the target
+        let mut automaton = GridAutomaton::new(blinker, Neighbourhood::VonNeumann, life);
+        assert_eq!(automaton.fast_forward(1), None);
+        assert_eq!(automaton.generation(), 1);
+    }
+
+    #[test]
+    fn test_sparse_automaton() {
+        let glider: SparseGrid<()> = SparseGrid::from_vec2d(
+            &Vec2d::from_rows(&[".#.", "..#", "###"], |c| c == '#'),
+            |on| *on,
+        )
+        .iter()
+        .map(|(point, _)| (point, ()))
+        .collect();
+        let mut automaton = SparseAutomaton::new(glider.clone(), Neighbourhood::Moore, sparse_life);
+
+        // A glider moves one cell diagonally every four generations
+        automaton.run(8);
+        let moved: Vec<Point> = automaton.grid().iter().map(|(p, _)| p).collect();
+        let expected: Vec<Point> = glider.iter().map(|(p, _)| p + Point::new(2, 2)).collect();
+        assert_eq!(moved, expected);
+        assert_eq!(automaton.fast_forward(20), None);
+        assert_eq!(automaton.generation(), 20);
+
+        let blinker: SparseGrid<()> = [-1, 0, 1]
+            .map(|x| (Point::new(x, 0), ()))
+            .into_iter()
+            .collect();
+        let mut automaton = SparseAutomaton::new(blinker, Neighbourhood::Moore, sparse_life);
+        assert_eq!(
+            automaton.fast_forward(1_000_000_001),
+            Some(Cycle {
+                start: 0,
+                length: 2
+            })
+        );
+        assert!(automaton.grid().contains(Point::new(0, -1)));
+        assert_eq!(
+            automaton.grid().bounds(),
+            Some((Point::new(0, -1), Point::new(0, 1)))
+        );
+    }
+}
@@ -1,11 +1,29 @@
+mod automaton;
 mod multiset;
+mod ocr;
 mod pairs;
 mod parser;
 mod permute;
+mod point;
+mod point_n;
+mod region;
+mod search;
+mod sparse_grid;
+mod symmetry;
 mod vec2d;
+mod view;
 
+pub use automaton::*;
 pub use multiset::*;
+pub use ocr::*;
 pub use pairs::*;
 pub use parser::*;
 pub use permute::*;
+pub use point::*;
+pub use point_n::*;
+pub use region::*;
+pub use search::*;
+pub use sparse_grid::*;
+pub use symmetry::*;
 pub use vec2d::*;
+pub use view::*;
@@ -0,0 +1,314 @@
+use super::{Point, Position, SparseGrid, Vec2d};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum OcrError {
+    #[error("No pixels are lit")]
+    Empty,
+
+    #[error("No known font is {0} pixels high")]
+    UnknownHeight(usize),
+
+    #[error("Unknown glyphs in {text}:\n{}", .glyphs.join("\n\n"))]
+    UnknownGlyphs {
+        /// The recognised letters with `?` for each unknown glyph
+        text: String,
+        /// The unknown glyphs drawn with `#` and `.`
+        glyphs: Vec<String>,
+    },
+}
+
+/// A block letter font. Glyphs are stored without blank columns on either side.
+struct Font {
+    height: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+/// The font of most puzzles, e.g. 2016 day 8, 2019 days 8 and 11, 2021 day 13 and 2022 day 10
+const SMALL: Font = Font {
+    height: 6,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+/// The font of 2018 day 10
+#[rustfmt::skip]
+const LARGE: Font = Font {
+    height: 10,
+    glyphs: &[
+        ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#",
+                "######", "#....#", "#....#", "#....#", "#....#"]),
+        ('B', &["#####.", "#....#", "#....#", "#....#", "#####.",
+                "#....#", "#....#", "#....#", "#....#", "#####."]),
+        ('C', &[".####.", "#....#", "#.....", "#.....", "#.....",
+                "#.....", "#.....", "#.....", "#....#", ".####."]),
+        ('E', &["######", "#.....", "#.....", "#.....", "#####.",
+                "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('F', &["######", "#.....", "#.....", "#.....", "#####.",
+                "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('G', &[".####.", "#....#", "#.....", "#.....", "#.....",
+                "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+        ('H', &["#....#", "#....#", "#....#", "#....#", "######",
+                "#....#", "#....#", "#....#", "#....#", "#....#"]),
+        ('J', &["...###", "....#.", "....#.", "....#.", "....#.",
+                "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+        ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....",
+                "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+        ('L', &["#.....", "#.....", "#.....", "#.....", "#.....",
+                "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+                "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+        ('P', &["#####.", "#....#", "#....#", "#....#", "#####.",
+                "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('R', &["#####.", "#....#", "#....#", "#....#", "#####.",
+                "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+        ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+                "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+        ('Z', &["######", ".....#", ".....#", "....#.", "...#..",
+                "..#...", ".#....", "#.....", "#.....", "######"]),
+    ],
+};
+
+const FONTS: [Font; 2] = [SMALL, LARGE];
+
+/// A glyph as one bitmask per column, bit `n` set if row `n` is lit.
+type Columns = Vec<u16>;
+
+impl Font {
+    fn columns(rows: &[&str]) -> Columns {
+        (0..rows[0].len())
+            .map(|x| {
+                rows.iter()
+                    .enumerate()
+                    .filter(|(_, row)| row.as_bytes()[x] == b'#')
+                    .fold(0, |mask, (y, _)| mask | 1 << y)
+            })
+            .collect()
+    }
+
+    /// The letters of a run of columns without a blank one. Letters usually have a blank column
+    /// between them, but not always, e.g. in 2016 day 8 a `Y` can touch the next letter.
+    fn recognise(&self, columns: &[u16]) -> Option<String> {
+        if columns.is_empty() {
+            return Some(String::new());
+        }
+
+        self.glyphs.iter().find_map(|(letter, rows)| {
+            let glyph = Self::columns(rows);
+            let rest = columns.strip_prefix(glyph.as_slice())?;
+            self.recognise(rest).map(|rest| format!("{letter}{rest}"))
+        })
+    }
+
+    fn draw(&self, columns: &[u16]) -> String {
+        (0..self.height)
+            .map(|y| {
+                columns
+                    .iter()
+                    .map(|mask| if mask & 1 << y != 0 { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Reads the block letters that some puzzles draw as their answer, in one of the known fonts.
+/// Blank rows and columns around the letters are ignored.
+pub fn ocr(pixels: &Vec2d<bool>) -> Result<String, OcrError> {
+    let lit_rows: Vec<usize> = (0..pixels.height())
+        .filter(|y| pixels.row(*y).contains(&true))
+        .collect();
+    let (Some(top), Some(bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Err(OcrError::Empty);
+    };
+
+    let height = bottom - top + 1;
+    let font = FONTS
+        .iter()
+        .find(|font| font.height == height)
+        .ok_or(OcrError::UnknownHeight(height))?;
+
+    let columns: Columns = (0..pixels.width())
+        .map(|x| {
+            (0..height)
+                .filter(|y| pixels[Position::new(x, top + y)])
+                .fold(0, |mask, y| mask | 1 << y)
+        })
+        .collect();
+
+    let mut text = String::new();
+    let mut unknown = Vec::new();
+    for run in columns
+        .split(|mask| *mask == 0)
+        .filter(|run| !run.is_empty())
+    {
+        match font.recognise(run) {
+            Some(letters) => text.push_str(&letters),
+            None => {
+                text.push('?');
+                unknown.push(font.draw(run));
+            }
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::UnknownGlyphs {
+            text,
+            glyphs: unknown,
+        })
+    }
+}
+
+/// Like [`ocr`] for the lit pixels of a sparse image, e.g. the keys of a [`SparseGrid`].
+pub fn ocr_points(points: impl IntoIterator<Item = Point>) -> Result<String, OcrError> {
+    let grid: SparseGrid<bool> = points.into_iter().map(|point| (point, true)).collect();
+    let pixels = grid.to_vec2d(false).map_err(|_| OcrError::Empty)?;
+    ocr(&pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_font() {
+        let pixels = Vec2d::from_rows(
+            &[
+                ".......................",
+                ".###..#..#..##..####...",
+                ".#..#.#..#.#..#.#......",
+                ".#..#.#..#.#....###....",
+                ".###..#..#.#.##.#......",
+                ".#.#..#..#.#..#.#......",
+                ".#..#..##...###.####...",
+            ],
+            |c| c == '#',
+        );
+        assert_eq!(ocr(&pixels), Ok("RUGE".to_string()));
+
+        // Every glyph of the font, one blank column apart
+        let mut rows = vec![String::new(); 6];
+        for (_, glyph) in SMALL.glyphs {
+            for (row, line) in rows.iter_mut().zip(glyph.iter()) {
+                row.push_str(line);
+                row.push('.');
+            }
+        }
+        let rows: Vec<&str> = rows.iter().map(|row| row.as_str()).collect();
+        assert_eq!(
+            ocr(&Vec2d::from_rows(&rows, |c| c == '#')),
+            Ok("ABCEFGHIJKLOPRSUYZ".to_string())
+        );
+
+        // No blank column between the letters
+
+        let touching = Vec2d::from_rows(
+            &[
+                "#...##..#",
+                "#...##..#",
+                ".#.#.####",
+                "..#..#..#",
+                "..#..#..#",
+                "..#..#..#",
+            ],
+            |c| c == '#',
+        );
+        assert_eq!(ocr(&touching), Ok("YH".to_string()));
+    }
+
+    #[test]
+    fn test_large_font() {
+        let pixels = Vec2d::from_rows(
+            &[
+                "#....#..######",
+                "#....#..#.....",
+                "#....#..#.....",
+                "#....#..#.....",
+                "######..#####.",
+                "#....#..#.....",
+                "#....#..#.....",
+                "#....#..#.....",
+                "#....#..#.....",
+                "#....#..######",
+            ],
+            |c| c == '#',
+        );
+        assert_eq!(ocr(&pixels), Ok("HE".to_string()));
+    }
+
+    #[test]
+    fn test_points() {
+        let points = [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (0, 4),
+            (0, 5),
+            (1, 5),
+            (2, 5),
+            (3, 5),
+        ]
+        .map(|(x, y)| Point::new(x - 10, y + 3));
+        assert_eq!(ocr_points(points), Ok("L".to_string()));
+        assert_eq!(ocr_points([]), Err(OcrError::Empty));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            ocr(&Vec2d::from_rows(&["...", "..."], |c| c == '#')),
+            Err(OcrError::Empty)
+        );
+        assert_eq!(
+            ocr(&Vec2d::from_rows(&["#", "#", "#"], |c| c == '#')),
+            Err(OcrError::UnknownHeight(3))
+        );
+
+        let pixels = Vec2d::from_rows(
+            &[
+                "#..#.#...#",
+                "#..#.##..#",
+                "####.#.#.#",
+                "#..#.#..##",
+                "#..#.#...#",
+                "#..#.#...#",
+            ],
+            |c| c == '#',
+        );
+        let error = ocr(&pixels).unwrap_err();
+        assert_eq!(
+            error,
+            OcrError::UnknownGlyphs {
+                text: "H?".to_string(),
+                glyphs: vec!["#...#\n##..#\n#.#.#\n#..##\n#...#\n#...#".to_string()],
+            }
+        );
+        assert!(
+            error
+                .to_string()
+                .starts_with("Unknown glyphs in H?:\n#...#\n")
+        );
+    }
+}
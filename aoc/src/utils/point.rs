@@ -0,0 +1,221 @@
+use super::{Direction, Distance, Position, Vec2dError};
+use crate::AoCError;
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// A signed position on an unbounded grid. Like [`Position`], `y` grows southwards.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl FromStr for Point {
+    type Err = AoCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s.split_once(',').ok_or(AoCError::BadInput)?;
+        Ok(Self::new(x.trim().parse()?, y.trim().parse()?))
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Direction) -> Self {
+        self + Self::from(rhs)
+    }
+}
+
+impl Sub<Direction> for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Direction) -> Self {
+        self - Self::from(rhs)
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(direction: Direction) -> Self {
+        Self::from(Distance::from(direction))
+    }
+}
+
+impl From<Distance> for Point {
+    fn from(distance: Distance) -> Self {
+        Self::new(distance.dx as i64, distance.dy as i64)
+    }
+}
+
+impl From<Position> for Point {
+    fn from(pos: Position) -> Self {
+        Self::new(pos.x as i64, pos.y as i64)
+    }
+}
+
+impl TryFrom<Point> for Position {
+    type Error = Vec2dError;
+
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            point.x.try_into().map_err(|_| Vec2dError::OutOfBounds)?,
+            point.y.try_into().map_err(|_| Vec2dError::OutOfBounds)?,
+        ))
+    }
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Distance from the origin moving north, east, south or west.
+    pub fn manhattan(&self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    /// Distance from the origin moving in all eight directions.
+    pub fn chebyshev(&self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    /// Each coordinate reduced to -1, 0 or 1, a single step towards the point.
+    pub fn signum(&self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Wraps each coordinate into `0..size`, e.g. for a torus shaped grid.
+    pub fn rem_euclid(&self, size: Self) -> Self {
+        Self::new(self.x.rem_euclid(size.x), self.y.rem_euclid(size.y))
+    }
+
+    /// The point a quarter turn clockwise around the origin.
+    pub fn rotate_cv(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// The point a quarter turn counterclockwise around the origin.
+    pub fn rotate_ccv(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// The north, east, south and west neighbours.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Direction::CARDINAL.into_iter().map(move |d| self + d)
+    }
+
+    /// The neighbours in all eight directions.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Direction::ALL.into_iter().map(move |d| self + d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let mut p = Point::new(3, -4);
+        assert_eq!(p + Point::new(-5, 1), Point::new(-2, -3));
+        assert_eq!(p - Point::new(-5, 1), Point::new(8, -5));
+        assert_eq!(-p, Point::new(-3, 4));
+        assert_eq!(p * 3, Point::new(9, -12));
+        assert_eq!(p.manhattan(), 7);
+        assert_eq!(p.chebyshev(), 4);
+        assert_eq!(p.signum(), Point::new(1, -1));
+
+        p += Direction::North;
+        assert_eq!(p, Point::new(3, -5));
+        assert_eq!(p - Direction::West, Point::new(4, -5));
+        assert_eq!(
+            Point::new(-1, 7).rem_euclid(Point::new(5, 4)),
+            Point::new(4, 3)
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        // North turns east
+        let p = Point::from(Direction::North);
+        assert_eq!(p.rotate_cv(), Point::from(Direction::East));
+        assert_eq!(p.rotate_ccv(), Point::from(Direction::West));
+        assert_eq!(p.rotate_cv().rotate_ccv(), p);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let p: Point = "-12, 7".parse().unwrap();
+        assert_eq!(p, Point::new(-12, 7));
+        assert_eq!(p.to_string(), "-12,7");
+        assert!(Position::try_from(p).is_err());
+        assert_eq!(
+            Position::try_from(Point::new(12, 7)).unwrap(),
+            Position::new(12, 7)
+        );
+        assert_eq!(Point::from(Position::new(1, 2)), Point::new(1, 2));
+        assert_eq!(Point::ORIGIN.neighbours8().count(), 8);
+        assert!(Point::ORIGIN.neighbours4().all(|p| p.manhattan() == 1));
+    }
+}
@@ -0,0 +1,432 @@
+use super::Point;
+use crate::AoCError;
+use std::{
+    collections::{HashSet, hash_set},
+    fmt::Display,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// A point with `N` signed coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PointN<const N: usize>(pub [i64; N]);
+
+pub type Point3 = PointN<3>;
+pub type Point4 = PointN<4>;
+
+impl<const N: usize> Default for PointN<N> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const N: usize> Display for PointN<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses exactly `N` comma separated coordinates, e.g. `162,817,812`.
+impl<const N: usize> FromStr for PointN<N> {
+    type Err = AoCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut point = Self::ORIGIN;
+        let mut values = s.split(',');
+        for c in point.0.iter_mut() {
+            *c = values.next().ok_or(AoCError::BadInput)?.trim().parse()?;
+        }
+        if values.next().is_some() {
+            return Err(AoCError::BadInput);
+        }
+        Ok(point)
+    }
+}
+
+impl<const N: usize> Index<usize> for PointN<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for PointN<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> Add for PointN<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<const N: usize> Sub for PointN<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<const N: usize> AddAssign for PointN<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> SubAssign for PointN<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Neg for PointN<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+impl<const N: usize> Mul<i64> for PointN<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self {
+        Self(self.0.map(|c| c * rhs))
+    }
+}
+
+impl From<Point> for PointN<2> {
+    fn from(point: Point) -> Self {
+        Self([point.x, point.y])
+    }
+}
+
+impl From<PointN<2>> for Point {
+    fn from(point: PointN<2>) -> Self {
+        Self::new(point[0], point[1])
+    }
+}
+
+impl<const N: usize> PointN<N> {
+    pub const ORIGIN: Self = Self([0; N]);
+
+    pub fn new(coordinates: [i64; N]) -> Self {
+        Self(coordinates)
+    }
+
+    pub fn manhattan(&self, other: &Self) -> u64 {
+        self.0.iter().zip(other.0).map(|(a, b)| a.abs_diff(b)).sum()
+    }
+
+    /// The squared straight line distance, exact unlike [`PointN::euclidean`].
+    pub fn euclidean_squared(&self, other: &Self) -> u64 {
+        self.0
+            .iter()
+            .zip(other.0)
+            .map(|(a, b)| a.abs_diff(b).pow(2))
+            .sum()
+    }
+
+    pub fn euclidean(&self, other: &Self) -> f64 {
+        (self.euclidean_squared(other) as f64).sqrt()
+    }
+
+    /// Distance moving diagonally as well, the largest difference along any axis.
+    pub fn chebyshev(&self, other: &Self) -> u64 {
+        self.0
+            .iter()
+            .zip(other.0)
+            .map(|(a, b)| a.abs_diff(b))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// The 2N points one step away along a single axis.
+    pub fn orthogonal_neighbours(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |axis| {
+            [-1, 1].map(|delta| {
+                let mut point = self;
+                point[axis] += delta;
+                point
+            })
+        })
+    }
+
+    /// The 3^N - 1 points that differ by at most one along every axis.
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        (0..3usize.pow(N as u32)).filter_map(move |mut n| {
+            let mut point = self;
+            for c in point.0.iter_mut() {
+                *c += (n % 3) as i64 - 1;
+                n /= 3;
+            }
+            (point != self).then_some(point)
+        })
+    }
+}
+
+impl PointN<3> {
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> i64 {
+        self.0[2]
+    }
+}
+
+/// An axis-aligned box, both corners inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<const N: usize> {
+    pub min: PointN<N>,
+    pub max: PointN<N>,
+}
+
+impl<const N: usize> BoundingBox<N> {
+    /// The box with `a` and `b` as opposite corners.
+    pub fn new(a: PointN<N>, b: PointN<N>) -> Self {
+        Self {
+            min: PointN(std::array::from_fn(|i| a[i].min(b[i]))),
+            max: PointN(std::array::from_fn(|i| a[i].max(b[i]))),
+        }
+    }
+
+    /// The smallest box containing all `points`, or `None` if there are none.
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a PointN<N>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        Some(points.fold(Self::new(first, first), |b, p| b.expanded(p)))
+    }
+
+    /// The smallest box containing this one and `point`.
+    pub fn expanded(&self, point: &PointN<N>) -> Self {
+        Self {
+            min: PointN(std::array::from_fn(|i| self.min[i].min(point[i]))),
+            max: PointN(std::array::from_fn(|i| self.max[i].max(point[i]))),
+        }
+    }
+
+    pub fn contains(&self, point: &PointN<N>) -> bool {
+        (0..N).all(|i| (self.min[i]..=self.max[i]).contains(&point[i]))
+    }
+
+    /// The overlap of the two boxes, or `None` if they do not touch.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = PointN(std::array::from_fn(|i| self.min[i].max(other.min[i])));
+        let max = PointN(std::array::from_fn(|i| self.max[i].min(other.max[i])));
+        (0..N)
+            .all(|i| min[i] <= max[i])
+            .then_some(Self { min, max })
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Length along each axis.
+    pub fn lengths(&self) -> [u64; N] {
+        std::array::from_fn(|i| self.min[i].abs_diff(self.max[i]) + 1)
+    }
+
+    /// Number of points inside the box.
+    pub fn volume(&self) -> u64 {
+        self.lengths().iter().product()
+    }
+
+    /// Manhattan distance from `point` to the closest point in the box, 0 if it is inside.
+    pub fn manhattan(&self, point: &PointN<N>) -> u64 {
+        (0..N)
+            .map(|i| point[i].abs_diff(point[i].clamp(self.min[i], self.max[i])))
+            .sum()
+    }
+}
+
+/// A sparse set of points, e.g. the active cubes of an unbounded N-dimensional grid.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PointSet<const N: usize> {
+    points: HashSet<PointN<N>>,
+}
+
+impl<const N: usize> FromIterator<PointN<N>> for PointSet<N> {
+    fn from_iter<I: IntoIterator<Item = PointN<N>>>(iter: I) -> Self {
+        Self {
+            points: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<const N: usize> PointSet<N> {
+    pub fn new() -> Self {
+        Self {
+            points: HashSet::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn contains(&self, point: &PointN<N>) -> bool {
+        self.points.contains(point)
+    }
+
+    /// Returns true if the point was not in the set.
+    pub fn insert(&mut self, point: PointN<N>) -> bool {
+        self.points.insert(point)
+    }
+
+    /// Returns true if the point was in the set.
+    pub fn remove(&mut self, point: &PointN<N>) -> bool {
+        self.points.remove(point)
+    }
+
+    pub fn iter(&self) -> hash_set::Iter<'_, PointN<N>> {
+        self.points.iter()
+    }
+
+    pub fn bounds(&self) -> Option<BoundingBox<N>> {
+        BoundingBox::from_points(&self.points)
+    }
+
+    /// Number of points in the set among the [`PointN::neighbours`] of `point`.
+    pub fn count_neighbours(&self, point: &PointN<N>) -> usize {
+        point.neighbours().filter(|p| self.contains(p)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{Automaton, Cycle, PointAutomaton};
+
+    #[test]
+    fn test_point() {
+        let a: Point3 = "162,817,812".parse().unwrap();
+        let b = Point3::new([57, 618, 57]);
+        assert_eq!(a.to_string(), "162,817,812");
+        assert_eq!(a - b, Point3::new([105, 199, 755]));
+        assert_eq!(a + -a, Point3::ORIGIN);
+        assert_eq!(b * 2, Point3::new([114, 1236, 114]));
+        assert_eq!(a[2], 812);
+        assert_eq!((a.x(), a.y(), a.z()), (162, 817, 812));
+
+        assert_eq!(a.manhattan(&b), 1059);
+        assert_eq!(a.chebyshev(&b), 755);
+        assert_eq!(a.euclidean_squared(&b), 105 * 105 + 199 * 199 + 755 * 755);
+        assert!((Point3::ORIGIN.euclidean(&Point3::new([2, 3, 6])) - 7.0).abs() < 1e-9);
+
+        assert!("1,2".parse::<Point3>().is_err());
+        assert!("1,2,3,4".parse::<Point3>().is_err());
+        assert_eq!(PointN::from(Point::new(3, -4)), PointN::new([3, -4]));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let origin = Point4::ORIGIN;
+        assert_eq!(origin.neighbours().count(), 80);
+        assert!(origin.neighbours().all(|p| origin.chebyshev(&p) == 1));
+        assert_eq!(origin.orthogonal_neighbours().count(), 8);
+        assert!(
+            origin
+                .orthogonal_neighbours()
+                .all(|p| origin.manhattan(&p) == 1)
+        );
+
+        let neighbours: HashSet<Point3> = Point3::new([1, 1, 1]).neighbours().collect();
+        assert_eq!(neighbours.len(), 26);
+        assert!(!neighbours.contains(&Point3::new([1, 1, 1])));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let a = BoundingBox::new(Point3::new([0, 0, 0]), Point3::new([3, 4, 5]));
+        let b = BoundingBox::new(Point3::new([5, 5, 5]), Point3::new([2, -1, 3]));
+        assert_eq!(b.min, Point3::new([2, -1, 3]));
+        assert_eq!(a.volume(), 120);
+
+        let overlap = a.intersection(&b).unwrap();
+        assert_eq!(
+            overlap,
+            BoundingBox::new(Point3::new([2, 0, 3]), Point3::new([3, 4, 5]))
+        );
+        assert_eq!(overlap.lengths(), [2, 5, 3]);
+        assert!(a.contains(&Point3::new([3, 0, 5])));
+        assert!(!a.contains(&Point3::new([3, 0, 6])));
+
+        let far = BoundingBox::new(Point3::new([4, 0, 0]), Point3::new([4, 0, 0]));
+        assert!(!a.intersects(&far));
+        assert_eq!(a.manhattan(&Point3::new([5, -2, 2])), 4);
+        assert_eq!(a.manhattan(&Point3::new([1, 1, 1])), 0);
+    }
+
+    /// The active cubes of a 2D slice, on the plane where all other coordinates are 0.
+    fn slice<const N: usize>(rows: &[&str]) -> PointSet<N> {
+        rows.iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(x, _)| {
+                        let mut point = PointN::ORIGIN;
+                        (point[0], point[1]) = (x as i64, y as i64);
+                        point
+                    })
+            })
+            .collect()
+    }
+
+    fn conway(active: bool, count: usize) -> bool {
+        count == 3 || (active && count == 2)
+    }
+
+    #[test]
+    fn test_conway_cubes() {
+        // 2020 day 17
+        let rows = [".#.", "..#", "###"];
+
+        let cubes: PointSet<3> = slice(&rows);
+        assert_eq!(cubes.len(), 5);
+        assert_eq!(cubes.bounds().unwrap().lengths(), [3, 3, 1]);
+        assert_eq!(cubes.count_neighbours(&Point3::new([1, 1, 0])), 5);
+        let mut automaton = PointAutomaton::new(cubes, conway);
+        automaton.run(6);
+        assert_eq!(automaton.points().len(), 112);
+
+        let mut automaton = PointAutomaton::new(slice::<4>(&rows), conway);
+        automaton.run(6);
+        assert_eq!(automaton.points().len(), 848);
+
+        // A lone cube dies and the empty space stays empty
+        let mut automaton = PointAutomaton::new(slice::<3>(&["#"]), conway);
+        assert_eq!(
+            automaton.fast_forward(1000),
+            Some(Cycle {
+                start: 1,
+                length: 1
+            })
+        );
+        assert_eq!(automaton.generation(), 1000);
+        assert!(automaton.points().is_empty());
+    }
+}
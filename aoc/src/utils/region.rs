@@ -0,0 +1,282 @@
+use super::{Direction, Position, Size, Vec2d};
+use std::collections::HashSet;
+
+/// A set of connected positions, e.g. a plot of garden.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Region {
+    positions: HashSet<Position>,
+}
+
+impl FromIterator<Position> for Region {
+    fn from_iter<I: IntoIterator<Item = Position>>(iter: I) -> Self {
+        Self {
+            positions: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Region {
+    pub fn contains(&self, pos: &Position) -> bool {
+        self.positions.contains(pos)
+    }
+
+    pub fn positions(&self) -> &HashSet<Position> {
+        &self.positions
+    }
+
+    pub fn area(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Number of cell edges between the region and anything outside it.
+    pub fn perimeter(&self) -> usize {
+        self.positions
+            .iter()
+            .map(|pos| {
+                Direction::CARDINAL
+                    .iter()
+                    .filter(|direction| !self.has_neighbour(*pos, **direction))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of corners along the outline, including the outlines of any holes.
+    pub fn corners(&self) -> usize {
+        const CORNERS: [(Direction, Direction, Direction); 4] = [
+            (Direction::North, Direction::East, Direction::NorthEast),
+            (Direction::East, Direction::South, Direction::SouthEast),
+            (Direction::South, Direction::West, Direction::SouthWest),
+            (Direction::West, Direction::North, Direction::NorthWest),
+        ];
+
+        self.positions
+            .iter()
+            .map(|pos| {
+                CORNERS
+                    .iter()
+                    .filter(|(a, b, diagonal)| {
+                        let a = self.has_neighbour(*pos, *a);
+                        let b = self.has_neighbour(*pos, *b);
+                        // Outer corner, or inner corner where the diagonal is missing
+                        (!a && !b) || (a && b && !self.has_neighbour(*pos, *diagonal))
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of straight sides, which is the same as the number of corners.
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+
+    fn has_neighbour(&self, pos: Position, direction: Direction) -> bool {
+        // A region is not tied to the size of a grid, only steps below zero leave it
+        const UNBOUNDED: Size = Size {
+            width: usize::MAX,
+            height: usize::MAX,
+        };
+        pos.step(direction, &UNBOUNDED)
+            .is_some_and(|next| self.contains(&next))
+    }
+}
+
+/// Connected components of a [`Vec2d`], see [`Vec2d::regions`].
+#[derive(Debug, Clone)]
+pub struct Regions {
+    labels: Vec2d<usize>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// The label of the region `pos` belongs to, an index into [`Regions::regions`].
+    pub fn label(&self, pos: Position) -> Option<usize> {
+        self.labels.get(pos).copied()
+    }
+
+    pub fn labels(&self) -> &Vec2d<usize> {
+        &self.labels
+    }
+
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    pub fn into_regions(self) -> Vec<Region> {
+        self.regions
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+impl<T> Vec2d<T> {
+    /// All positions reachable from `start` moving north, east, south or west whenever
+    /// `connected(from, to)` holds for the two cells.
+    pub fn flood_fill(&self, start: Position, connected: impl Fn(&T, &T) -> bool) -> Region {
+        let mut positions = HashSet::new();
+        if self.get(start).is_none() {
+            return Region { positions };
+        }
+
+        let mut stack = vec![start];
+        positions.insert(start);
+        while let Some(pos) = stack.pop() {
+            for (next, item) in self.neighbours4(pos) {
+                if connected(&self[pos], item) && positions.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        Region { positions }
+    }
+
+    /// Splits the grid into regions of cells that are `connected` to a neighbour, labelled in
+    /// the order their first cell appears in reading order.
+    pub fn regions(&self, connected: impl Fn(&T, &T) -> bool) -> Regions {
+        let mut labels: Vec2d<Option<usize>> = self.map(|_| None);
+        let mut regions = Vec::new();
+
+        for (start, _) in self.iter() {
+            if labels[start].is_some() {
+                continue;
+            }
+
+            let region = self.flood_fill(start, &connected);
+            for pos in region.positions() {
+                labels[*pos] = Some(regions.len());
+            }
+            regions.push(region);
+        }
+
+        Regions {
+            labels: labels.map(|label| label.unwrap_or_default()),
+            regions,
+        }
+    }
+
+    /// Cells that are not walls and cannot reach the edge of the grid without crossing one,
+    /// moving north, east, south or west.
+    pub fn enclosed(&self, wall: impl Fn(&T) -> bool) -> Vec2d<bool> {
+        let mut outside = self.map(|_| false);
+        let mut stack: Vec<Position> = self
+            .iter()
+            .filter(|(pos, item)| {
+                (pos.x == 0
+                    || pos.y == 0
+                    || pos.x == self.width() - 1
+                    || pos.y == self.height() - 1)
+                    && !wall(item)
+            })
+            .map(|(pos, _)| pos)
+            .collect();
+        for pos in &stack {
+            outside[*pos] = true;
+        }
+
+        while let Some(pos) = stack.pop() {
+            for (next, item) in self.neighbours4(pos) {
+                if !wall(item) && !outside[next] {
+                    outside[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        let mut enclosed = self.map(|item| !wall(item));
+        for (pos, outside) in outside.iter() {
+            if *outside {
+                enclosed[pos] = false;
+            }
+        }
+        enclosed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regions() {
+        // 2024 day 12
+        let garden = Vec2d::from_rows(&["AAAA", "BBCD", "BBCC", "EEEC"], |c| c);
+        let regions = garden.regions(|a, b| a == b);
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions.label(Position::new(0, 0)), Some(0));
+        assert_eq!(regions.label(Position::new(1, 2)), Some(1));
+        assert_eq!(regions.label(Position::new(3, 3)), Some(2));
+        assert_eq!(regions.label(Position::new(4, 0)), None);
+
+        let measures: Vec<_> = regions
+            .regions()
+            .iter()
+            .map(|r| (r.area(), r.perimeter(), r.sides()))
+            .collect();
+        assert_eq!(
+            measures,
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+    }
+
+    #[test]
+    fn test_holes() {
+        let garden = Vec2d::from_rows(&["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"], |c| c);
+        let regions = garden.regions(|a, b| a == b);
+        assert_eq!(regions.len(), 5);
+
+        let outer = &regions.regions()[0];
+        assert_eq!(outer.area(), 21);
+        assert_eq!(outer.perimeter(), 36);
+        assert_eq!(outer.sides(), 20);
+
+        // Diagonal neighbours are not connected
+        let garden = Vec2d::from_rows(&["AB", "BA"], |c| c);
+        assert_eq!(garden.regions(|a, b| a == b).len(), 4);
+    }
+
+    #[test]
+    fn test_edges() {
+        // The cell west of x = 0 is not the one at x = usize::MAX
+        let region: Region = [Position::new(0, 0), Position::new(usize::MAX, 0)]
+            .into_iter()
+            .collect();
+        assert_eq!(region.perimeter(), 8);
+        assert_eq!(region.corners(), 8);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let maze = Vec2d::from_rows(&["..#..", "..#..", "###.."], |c| c);
+        let region = maze.flood_fill(Position::new(0, 0), |_, to| *to == '.');
+        assert_eq!(region.area(), 4);
+        assert!(region.contains(&Position::new(1, 1)));
+        assert!(!region.contains(&Position::new(3, 0)));
+
+        let region = maze.flood_fill(Position::new(5, 0), |_, _| true);
+        assert_eq!(region.area(), 0);
+    }
+
+    #[test]
+    fn test_enclosed() {
+        let map = Vec2d::from_rows(
+            &[".......", ".#####.", ".#..#..", ".####..", "......."],
+            |c| c,
+        );
+        let enclosed = map.enclosed(|c| *c == '#');
+        let cells: Vec<_> = enclosed
+            .iter()
+            .filter(|(_, e)| **e)
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(cells, vec![Position::new(2, 2), Position::new(3, 2)]);
+    }
+}
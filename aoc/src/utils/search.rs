@@ -0,0 +1,392 @@
+use super::{Position, Vec2d};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Distances and shortest path predecessors found by [`bfs`], [`dijkstra`] or [`astar`].
+///
+/// Every predecessor on a shortest path is kept, so [`Paths::all_paths`] can enumerate all
+/// of them and not just the first one found.
+#[derive(Debug, Clone)]
+pub struct Paths<S> {
+    distances: HashMap<S, usize>,
+    predecessors: HashMap<S, Vec<S>>,
+    targets: Vec<S>,
+}
+
+impl<S> Paths<S>
+where
+    S: Clone + Eq + Hash,
+{
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            targets: Vec::new(),
+        }
+    }
+
+    /// Records that `state` can be reached from `from` at `distance`. Returns true if this is
+    /// a new shortest distance and `state` needs to be visited.
+    fn relax(&mut self, from: &S, state: S, distance: usize) -> bool {
+        match self.distances.get(&state) {
+            Some(d) if *d < distance => false,
+            Some(d) if *d == distance => {
+                let predecessors = self.predecessors.entry(state).or_default();
+                if !predecessors.contains(from) {
+                    predecessors.push(from.clone());
+                }
+                false
+            }
+            _ => {
+                self.distances.insert(state.clone(), distance);
+                self.predecessors.insert(state, vec![from.clone()]);
+                true
+            }
+        }
+    }
+
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.distances.get(state).copied()
+    }
+
+    /// Every visited state and its shortest distance.
+    pub fn distances(&self) -> &HashMap<S, usize> {
+        &self.distances
+    }
+
+    /// The states `state` can be reached from on a shortest path, empty for a start.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// The goal states reached, all at the same shortest distance.
+    pub fn targets(&self) -> &[S] {
+        &self.targets
+    }
+
+    /// Distance to the closest goal.
+    pub fn cost(&self) -> Option<usize> {
+        self.distance(self.targets.first()?)
+    }
+
+    /// A shortest path from a start to `state`, both included.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        self.distances.get(state)?;
+
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path from a start to `state`. The number of paths can grow
+    /// exponentially, use [`Paths::on_shortest_paths`] if only the states are needed.
+    pub fn all_paths(&self, state: &S) -> Vec<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return vec![];
+        }
+
+        let predecessors = self.predecessors(state);
+        if predecessors.is_empty() {
+            return vec![vec![state.clone()]];
+        }
+
+        predecessors
+            .iter()
+            .flat_map(|previous| self.all_paths(previous))
+            .map(|mut path| {
+                path.push(state.clone());
+                path
+            })
+            .collect()
+    }
+
+    /// All states on any shortest path to `state`, including `state`.
+    pub fn on_shortest_paths(&self, state: &S) -> HashSet<S> {
+        let mut states = HashSet::new();
+        if !self.distances.contains_key(state) {
+            return states;
+        }
+
+        let mut stack = vec![state.clone()];
+        while let Some(state) = stack.pop() {
+            stack.extend(self.predecessors(&state).iter().cloned());
+            states.insert(state);
+        }
+        states
+    }
+}
+
+/// Breadth-first search where every step costs 1. Stops once all goal states at the shortest
+/// distance have been reached, or explores everything reachable if there is no goal.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Paths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if paths.distances.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+
+    let mut best = None;
+    while let Some(state) = queue.pop_front() {
+        let distance = paths.distances[&state];
+        if best.is_some_and(|best| distance > best) {
+            break;
+        }
+        if goal(&state) {
+            best = Some(distance);
+            paths.targets.push(state);
+            continue;
+        }
+
+        for next in successors(&state) {
+            if paths.relax(&state, next.clone(), distance + 1) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    paths
+}
+
+/// Dijkstra's algorithm, `successors` returns each next state with the cost of moving there.
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Paths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    astar(starts, successors, |_| 0, goal)
+}
+
+/// A* search. The `heuristic` must never overestimate the remaining cost to a goal, and must
+/// not drop by more than the cost of a step, for the distances and paths to be shortest.
+pub fn astar<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Paths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut paths = Paths::new();
+    // States are not necessarily ordered, so the heap refers to them by index
+    let mut states = Vec::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if paths.distances.insert(start.clone(), 0).is_none() {
+            queue.push(Reverse((heuristic(&start), 0, states.len())));
+            states.push(start);
+        }
+    }
+
+    let mut best = None;
+    while let Some(Reverse((estimate, distance, index))) = queue.pop() {
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+        let state = states[index].clone();
+        if paths.distances[&state] < distance {
+            // Reached again on a shorter path after it was queued
+            continue;
+        }
+        if goal(&state) {
+            best = Some(distance);
+            paths.targets.push(state);
+            continue;
+        }
+
+        for (next, cost) in successors(&state) {
+            let distance = distance + cost;
+            if paths.relax(&state, next.clone(), distance) {
+                queue.push(Reverse((
+                    distance + heuristic(&next),
+                    distance,
+                    states.len(),
+                )));
+                states.push(next);
+            }
+        }
+    }
+
+    paths
+}
+
+impl<T> Vec2d<T> {
+    /// [`bfs`] from `start` to every reachable position, moving north, east, south or west
+    /// whenever `passable(from, to)` holds for the two cells.
+    pub fn bfs(&self, start: Position, passable: impl Fn(&T, &T) -> bool) -> Paths<Position> {
+        let passable = &passable;
+        bfs(
+            [start],
+            |pos| {
+                let from = &self[*pos];
+                self.neighbours4(*pos)
+                    .filter(move |(_, to)| passable(from, to))
+                    .map(|(pos, _)| pos)
+            },
+            |_| false,
+        )
+    }
+
+    /// [`dijkstra`] from `start` to every reachable position, moving north, east, south or
+    /// west at the cost returned by `cost(from, to)`, or not at all if it is `None`.
+    pub fn dijkstra(
+        &self,
+        start: Position,
+        cost: impl Fn(&T, &T) -> Option<usize>,
+    ) -> Paths<Position> {
+        dijkstra([start], self.successors(&cost), |_| false)
+    }
+
+    /// [`astar`] from `start` to `target` using the Manhattan distance as heuristic, so every
+    /// step must cost at least 1.
+    pub fn astar(
+        &self,
+        start: Position,
+        target: Position,
+        cost: impl Fn(&T, &T) -> Option<usize>,
+    ) -> Paths<Position> {
+        astar(
+            [start],
+            self.successors(&cost),
+            |pos| (target - *pos).manhattan(),
+            |pos| *pos == target,
+        )
+    }
+
+    fn successors<'a>(
+        &'a self,
+        cost: &'a impl Fn(&T, &T) -> Option<usize>,
+    ) -> impl FnMut(&Position) -> Vec<(Position, usize)> + 'a {
+        move |pos| {
+            let from = &self[*pos];
+            self.neighbours4(*pos)
+                .filter_map(|(next, to)| Some((next, cost(from, to)?)))
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Direction;
+
+    #[test]
+    fn test_bfs() {
+        let maze = Vec2d::from_rows(&[".....", ".#.#.", "....."], |c| c);
+        let paths = maze.bfs(Position::new(0, 0), |_, to| *to != '#');
+
+        let end = Position::new(4, 2);
+        assert_eq!(paths.distance(&end), Some(6));
+        assert_eq!(paths.distance(&Position::new(2, 1)), Some(3));
+        assert_eq!(paths.distance(&Position::new(1, 1)), None);
+
+        let path = paths.path(&end).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&Position::new(0, 0)));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path.windows(2).all(|w| (w[1] - w[0]).manhattan() == 1));
+
+        // Down through any of the three gaps
+        assert_eq!(paths.all_paths(&end).len(), 3);
+        assert_eq!(paths.on_shortest_paths(&end).len(), 13);
+    }
+
+    #[test]
+    fn test_bfs_goal() {
+        let paths = bfs([1u64], |n| [n + 1, n * 2], |n| *n == 10);
+        assert_eq!(paths.cost(), Some(4));
+        assert_eq!(paths.targets(), &[10]);
+        assert_eq!(paths.all_paths(&10), vec![vec![1, 2, 4, 5, 10]]);
+        assert_eq!(paths.distance(&20), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // 2021 day 15
+        let cave = Vec2d::from_rows(
+            &[
+                "1163751742",
+                "1381373672",
+                "2136511328",
+                "3694931569",
+                "7463417111",
+                "1319128137",
+                "1359912421",
+                "3125421639",
+                "1293138521",
+                "2311944581",
+            ],
+            |c| c.to_digit(10).unwrap() as usize,
+        );
+        let end = Position::new(9, 9);
+
+        let paths = cave.dijkstra(Position::new(0, 0), |_, to| Some(*to));
+        assert_eq!(paths.distance(&end), Some(40));
+
+        let astar = cave.astar(Position::new(0, 0), end, |_, to| Some(*to));
+        assert_eq!(astar.cost(), Some(40));
+        assert_eq!(astar.targets(), &[end]);
+        assert!(astar.distances().len() <= paths.distances().len());
+    }
+
+    #[test]
+    fn test_states() {
+        // Turning costs 1000 and moving forward 1, like 2024 day 16
+        let maze = Vec2d::from_rows(
+            &["#######", "#S....#", "#.#.###", "#....E#", "#######"],
+            |c| c,
+        );
+        let start = (Position::new(1, 1), Direction::East);
+        let end = Position::new(5, 3);
+
+        let paths = dijkstra(
+            [start],
+            |&(pos, direction)| {
+                let mut next = vec![
+                    ((pos, direction.turn_cv().turn_cv()), 1000),
+                    ((pos, direction.turn_ccv().turn_ccv()), 1000),
+                ];
+                if let Some(pos) = pos.step(direction, maze.size())
+                    && maze[pos] != '#'
+                {
+                    next.push(((pos, direction), 1));
+                }
+                next
+            },
+            |(pos, _)| *pos == end,
+        );
+
+        assert_eq!(paths.cost(), Some(2006));
+        let target = paths.targets()[0];
+        assert_eq!(target, (end, Direction::East));
+        assert_eq!(paths.all_paths(&target).len(), 2);
+        let tiles: HashSet<Position> = paths
+            .on_shortest_paths(&target)
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(tiles.len(), 10);
+    }
+}
@@ -0,0 +1,229 @@
+use super::{Point, Position, Size, Vec2d, Vec2dError};
+use std::{
+    collections::{HashMap, hash_map},
+    fmt::Display,
+};
+
+/// An unbounded grid that only stores the cells that are set, e.g. for an infinite map or
+/// one that grows while it is explored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    min: Point,
+    max: Point,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        for (point, item) in iter {
+            self.insert(point, item);
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            min: Point::ORIGIN,
+            max: Point::ORIGIN,
+        }
+    }
+
+    /// The set cells of `vec2d` for which `keep` holds, at the same coordinates.
+    pub fn from_vec2d(vec2d: &Vec2d<T>, keep: impl Fn(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        vec2d
+            .iter()
+            .filter(|(_, item)| keep(item))
+            .map(|(pos, item)| (Point::from(pos), item.clone()))
+            .collect()
+    }
+
+    /// The bounding box as a dense grid with `background` in the cells that are not set. The
+    /// top left corner of the bounding box becomes position (0, 0).
+    pub fn to_vec2d(&self, background: T) -> Result<Vec2d<T>, Vec2dError>
+    where
+        T: Clone,
+    {
+        let (min, _) = self.bounds().ok_or(Vec2dError::Empty)?;
+        let mut vec2d = Vec2d::filled(self.size(), background)?;
+        for (point, item) in &self.cells {
+            vec2d[Position::try_from(*point - min)?] = item.clone();
+        }
+        Ok(vec2d)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// The smallest and largest corner of the set cells, both inclusive.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        (!self.is_empty()).then_some((self.min, self.max))
+    }
+
+    /// Width and height of the bounding box.
+    pub fn size(&self) -> Size {
+        match self.bounds() {
+            Some((min, max)) => {
+                Size::new((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize)
+            }
+            None => Size::new(0, 0),
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Sets a cell and returns its previous item.
+    pub fn insert(&mut self, point: Point, item: T) -> Option<T> {
+        if self.is_empty() {
+            (self.min, self.max) = (point, point);
+        } else {
+            self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+            self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+        }
+        self.cells.insert(point, item)
+    }
+
+    /// Clears a cell and returns its item. Shrinks the bounding box if needed, which takes
+    /// time linear in the number of cells when the point was on its edge.
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let item = self.cells.remove(&point)?;
+        if [self.min.x, self.max.x].contains(&point.x)
+            || [self.min.y, self.max.y].contains(&point.y)
+        {
+            self.update_bounds();
+        }
+        Some(item)
+    }
+
+    fn update_bounds(&mut self) {
+        let mut points = self.cells.keys();
+        let Some(first) = points.next() else {
+            return;
+        };
+
+        (self.min, self.max) = points.fold((*first, *first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        });
+    }
+
+    /// The set cells in reading order, row by row from the top.
+    pub fn iter(&self) -> std::vec::IntoIter<(Point, &T)> {
+        let mut cells: Vec<_> = self.cells.iter().map(|(p, item)| (*p, item)).collect();
+        cells.sort_unstable_by_key(|(p, _)| (p.y, p.x));
+        cells.into_iter()
+    }
+
+    /// The set cells in no particular order, cheaper than [`SparseGrid::iter`].
+    pub fn cells(&self) -> hash_map::Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+}
+
+/// Renders the bounding box with `.` in the cells that are not set.
+impl<T> Display for SparseGrid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                match self.get(Point::new(x, y)) {
+                    Some(item) => write!(f, "{}", item)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.to_string(), "");
+
+        grid.insert(Point::new(2, 1), '#');
+        grid.insert(Point::new(-1, -1), 'S');
+        assert_eq!(grid.insert(Point::new(0, 1), '#'), None);
+        assert_eq!(grid.insert(Point::new(0, 1), 'B'), Some('#'));
+
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.bounds(), Some((Point::new(-1, -1), Point::new(2, 1))));
+        assert_eq!(grid.size(), Size::new(4, 3));
+        assert_eq!(grid.to_string(), "S...\n....\n.B.#\n");
+
+        let order: Vec<Point> = grid.iter().map(|(p, _)| p).collect();
+        assert_eq!(
+            order,
+            vec![Point::new(-1, -1), Point::new(0, 1), Point::new(2, 1)]
+        );
+
+        assert_eq!(grid.remove(Point::new(-1, -1)), Some('S'));
+        assert_eq!(grid.remove(Point::new(-1, -1)), None);
+        assert_eq!(grid.bounds(), Some((Point::new(0, 1), Point::new(2, 1))));
+        assert_eq!(grid.to_string(), "B.#\n");
+    }
+
+    #[test]
+    fn test_vec2d() {
+        let vec2d = Vec2d::new(vec![vec!['.', '#'], vec!['#', '.']]).unwrap();
+        let mut grid = SparseGrid::from_vec2d(&vec2d, |c| *c == '#');
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains(Point::new(1, 0)));
+        assert_eq!(grid.to_vec2d('.').unwrap(), vec2d);
+
+        grid.insert(Point::new(-1, 0), '#');
+        assert_eq!(grid.to_vec2d('.').unwrap().to_string(), "#.#\n.#.\n");
+        assert!(SparseGrid::<char>::new().to_vec2d('.').is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use super::Vec2d;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use strum::{EnumIter, IntoEnumIterator};
+
+/// The eight ways a grid can be rotated and flipped onto itself, the dihedral group of the
+/// square. Rotations are clockwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirrored left to right
+    FlipHorizontal,
+    /// Mirrored top to bottom
+    FlipVertical,
+    /// Mirrored along the diagonal from the top left
+    Transpose,
+    /// Mirrored along the diagonal from the top right
+    AntiTranspose,
+}
+
+impl Symmetry {
+    /// Transforms `vec2d` in place.
+    pub fn apply_mut<T>(&self, vec2d: &mut Vec2d<T>) {
+        match self {
+            Self::Identity => {}
+            Self::Rotate90 => vec2d.rotate90(),
+            Self::Rotate180 => vec2d.rotate180(),
+            Self::Rotate270 => vec2d.rotate270(),
+            Self::FlipHorizontal => vec2d.flip_horizontal(),
+            Self::FlipVertical => vec2d.flip_vertical(),
+            Self::Transpose => vec2d.transpose(),
+            Self::AntiTranspose => {
+                vec2d.transpose();
+                vec2d.rotate180();
+            }
+        }
+    }
+
+    pub fn apply<T>(&self, vec2d: &Vec2d<T>) -> Vec2d<T>
+    where
+        T: Clone,
+    {
+        let mut vec2d = vec2d.clone();
+        self.apply_mut(&mut vec2d);
+        vec2d
+    }
+}
+
+impl<T> Vec2d<T> {
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate90(&mut self) {
+        self.transpose();
+        self.flip_horizontal();
+    }
+
+    pub fn rotate180(&mut self) {
+        self.as_mut_slice().reverse();
+    }
+
+    /// Rotates a quarter turn counterclockwise.
+    pub fn rotate270(&mut self) {
+        self.transpose();
+        self.flip_vertical();
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&mut self) {
+        for row in 0..self.height() {
+            self.row_mut(row).reverse();
+        }
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&mut self) {
+        let width = self.width();
+        let height = self.height();
+        let data = self.as_mut_slice();
+        for row in 0..height / 2 {
+            let (top, bottom) = data.split_at_mut((height - 1 - row) * width);
+            top[row * width..(row + 1) * width].swap_with_slice(&mut bottom[..width]);
+        }
+    }
+
+    /// All eight rotations and reflections of the grid, see [`Symmetry`].
+    pub fn variants(&self) -> impl Iterator<Item = (Symmetry, Vec2d<T>)> + '_
+    where
+        T: Clone,
+    {
+        Symmetry::iter().map(|symmetry| (symmetry, symmetry.apply(self)))
+    }
+
+    /// The smallest of the eight variants, so that grids that are rotations or reflections
+    /// of each other have the same canonical form.
+    pub fn canonical(&self) -> Vec2d<T>
+    where
+        T: Clone + Ord,
+    {
+        self.variants()
+            .map(|(_, variant)| variant)
+            .min_by(|a, b| (a.width(), a.as_slice()).cmp(&(b.width(), b.as_slice())))
+            .unwrap_or_else(|| self.clone())
+    }
+
+    /// A hash that is the same for all eight variants, for items that are not [`Ord`]. Use
+    /// [`Vec2d::canonical`] when collisions have to be ruled out.
+    pub fn canonical_hash(&self) -> u64
+    where
+        T: Clone + Hash,
+    {
+        self.variants()
+            .map(|(_, variant)| {
+                let mut hasher = DefaultHasher::new();
+                variant.hash(&mut hasher);
+                hasher.finish()
+            })
+            .min()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_rotate() {
+        let abc = Vec2d::from_rows(&["abc", "def"], |c| c);
+
+        let mut vec2d = abc.clone();
+        vec2d.rotate90();
+        assert_eq!(vec2d, Vec2d::from_rows(&["da", "eb", "fc"], |c| c));
+        vec2d.rotate90();
+        assert_eq!(vec2d, Vec2d::from_rows(&["fed", "cba"], |c| c));
+        vec2d.rotate90();
+        assert_eq!(vec2d, Vec2d::from_rows(&["cf", "be", "ad"], |c| c));
+        vec2d.rotate90();
+        assert_eq!(vec2d, abc);
+
+        let mut vec2d = abc.clone();
+        vec2d.rotate270();
+        assert_eq!(vec2d, Vec2d::from_rows(&["cf", "be", "ad"], |c| c));
+    }
+
+    #[test]
+    fn test_flip() {
+        let abc = Vec2d::from_rows(&["abc", "def", "ghi"], |c| c);
+
+        let mut vec2d = abc.clone();
+        vec2d.flip_horizontal();
+        assert_eq!(vec2d, Vec2d::from_rows(&["cba", "fed", "ihg"], |c| c));
+
+        let mut vec2d = abc.clone();
+        vec2d.flip_vertical();
+        assert_eq!(vec2d, Vec2d::from_rows(&["ghi", "def", "abc"], |c| c));
+
+        assert_eq!(
+            Symmetry::AntiTranspose.apply(&abc),
+            Vec2d::from_rows(&["ifc", "heb", "gda"], |c| c)
+        );
+    }
+
+    #[test]
+    fn test_variants() {
+        // 2017 day 21 glider, all its variants are distinct
+        let glider = Vec2d::from_rows(&[".#.", "..#", "###"], |c| c);
+        let variants: HashSet<Vec2d<char>> = glider.variants().map(|(_, v)| v).collect();
+        assert_eq!(variants.len(), 8);
+
+        let canonical = glider.canonical();
+        let hash = glider.canonical_hash();
+        for variant in &variants {
+            assert_eq!(variant.canonical(), canonical);
+            assert_eq!(variant.canonical_hash(), hash);
+        }
+        assert_ne!(
+            Vec2d::from_rows(&["#..", "...", "###"], |c| c).canonical(),
+            canonical
+        );
+
+        // All variants of a fully symmetric pattern are the same
+        let cross = Vec2d::from_rows(&[".#.", "###", ".#."], |c| c);
+        assert_eq!(cross.variants().filter(|(_, v)| *v == cross).count(), 8);
+    }
+}
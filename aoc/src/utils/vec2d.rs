@@ -1,8 +1,8 @@
 use crate::AoCError;
 use std::{
     fmt::Display,
-    iter::Flatten,
-    ops::{Add, Mul, Sub},
+    iter::StepBy,
+    ops::{Add, Index, IndexMut, Mul, Sub},
     slice,
     str::FromStr,
 };
@@ -19,9 +19,12 @@ pub enum Vec2dError {
 
     #[error("The position is out of bounds")]
     OutOfBounds,
+
+    #[error("Expected {expected} items but got {actual}")]
+    SizeMismatch { expected: usize, actual: usize },
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Size {
     pub width: usize,
     pub height: usize,
@@ -158,6 +161,16 @@ impl Position {
             y: self.x,
         }
     }
+
+    /// The neighbouring position in `direction`, or `None` if it is outside `size`.
+    pub fn step(self, direction: Direction, size: &Size) -> Option<Self> {
+        let Distance { dx, dy } = direction.into();
+        let pos = Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        };
+        pos.is_inside(size).then_some(pos)
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -223,6 +236,21 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// North, east, south and west
+    pub const CARDINAL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// All eight directions clockwise from north
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
     pub fn turn_cv(&self) -> Direction {
         match self {
             Self::North => Direction::NorthEast,
@@ -274,9 +302,10 @@ impl TryFrom<char> for Direction {
 
 /// A 2D vector that can be indexed with a `Position` struct.
 /// (0, 0) is the top-left corner with x increasing to the east and y increasing south.
-#[derive(Debug, Default, Clone)]
+/// Items are stored row by row in a single `Vec`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct Vec2d<T> {
-    data: Vec<Vec<T>>,
+    data: Vec<T>,
     size: Size,
 }
 
@@ -293,13 +322,56 @@ impl<T> Vec2d<T> {
             } else if data.iter().any(|row| row.len() != size.width) {
                 Err(Vec2dError::NotSquare)
             } else {
-                Ok(Self { data, size })
+                Ok(Self {
+                    data: data.into_iter().flatten().collect(),
+                    size,
+                })
             }
         } else {
             Err(Vec2dError::Empty)
         }
     }
 
+    /// Creates a Vec2d from items in row-major order.
+    pub fn from_vec(size: Size, data: Vec<T>) -> Result<Self, Vec2dError> {
+        if size.area() == 0 {
+            Err(Vec2dError::Empty)
+        } else if data.len() != size.area() {
+            Err(Vec2dError::SizeMismatch {
+                expected: size.area(),
+                actual: data.len(),
+            })
+        } else {
+            Ok(Self { data, size })
+        }
+    }
+
+    /// A grid from rows of text with `f` applied to every character, for tests.
+    #[cfg(test)]
+    pub(crate) fn from_rows(rows: &[&str], f: impl Fn(char) -> T) -> Self {
+        Self::new(
+            rows.iter()
+                .map(|row| row.chars().map(&f).collect())
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    pub fn filled(size: Size, item: T) -> Result<Self, Vec2dError>
+    where
+        T: Clone,
+    {
+        Self::from_vec(size, vec![item; size.area()])
+    }
+
+    /// A grid of the same size with `f` applied to every item.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Vec2d<U> {
+        Vec2d {
+            data: self.data.iter().map(f).collect(),
+            size: self.size,
+        }
+    }
+
     pub fn size(&self) -> &Size {
         &self.size
     }
@@ -312,35 +384,65 @@ impl<T> Vec2d<T> {
         self.size.height
     }
 
+    fn index_of(&self, pos: Position) -> Option<usize> {
+        self.size
+            .contains(pos)
+            .then(|| pos.y * self.size.width + pos.x)
+    }
+
     pub fn get(&self, pos: Position) -> Option<&T> {
-        self.data.get(pos.y)?.get(pos.x)
+        self.data.get(self.index_of(pos)?)
     }
 
-    pub fn set(&mut self, pos: Position, item: T) -> Result<(), Vec2dError> {
-        if !self.size.contains(pos) {
-            return Err(Vec2dError::OutOfBounds);
-        }
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        let index = self.index_of(pos)?;
+        self.data.get_mut(index)
+    }
 
-        self.data[pos.y][pos.x] = item;
+    pub fn set(&mut self, pos: Position, item: T) -> Result<(), Vec2dError> {
+        *self.get_mut(pos).ok_or(Vec2dError::OutOfBounds)? = item;
         Ok(())
     }
 
-    pub fn flatten(&self) -> Flatten<slice::Iter<'_, Vec<T>>> {
-        self.data.iter().flatten()
+    pub fn flatten(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// All items in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.size.width..(row + 1) * self.size.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.data[row * self.size.width..(row + 1) * self.size.width]
+    }
+
+    pub fn rows(&self) -> slice::ChunksExact<'_, T> {
+        self.data.chunks_exact(self.size.width)
+    }
+
+    pub fn col(&self, col: usize) -> StepBy<slice::Iter<'_, T>> {
+        assert!(
+            col < self.size.width,
+            "column {col} out of bounds for width {}",
+            self.size.width
+        );
+        self.data[col..].iter().step_by(self.size.width)
     }
 
     pub fn find(&self, item: &T) -> Option<(Position, &T)>
     where
         T: Eq,
     {
-        for p in self.size().iter() {
-            if let Some(i) = self.get(p)
-                && i == item
-            {
-                return Some((p, i));
-            }
-        }
-        None
+        self.iter().find(|(_, i)| *i == item)
     }
 
     pub fn iter(&self) -> Vec2dIter<'_, T> {
@@ -359,7 +461,7 @@ impl<T> Vec2d<T> {
 
     pub fn iter_row_mut(&mut self, row: usize) -> Vec2dRowIterMut<'_, T> {
         Vec2dRowIterMut {
-            iter: self.data[row].iter_mut(),
+            iter: self.row_mut(row).iter_mut(),
             row,
             col: 0,
         }
@@ -372,48 +474,104 @@ impl<T> Vec2d<T> {
         }
     }
 
-    pub fn data(&self) -> &Vec<Vec<T>> {
-        &self.data
+    /// The in-bounds north, east, south and west neighbours of `pos`.
+    pub fn neighbours4(&self, pos: Position) -> Vec2dNeighbours<'_, T> {
+        Vec2dNeighbours {
+            vec2d: self,
+            pos,
+            directions: Direction::CARDINAL.iter(),
+        }
+    }
+
+    /// The in-bounds neighbours of `pos` including diagonals.
+    pub fn neighbours8(&self, pos: Position) -> Vec2dNeighbours<'_, T> {
+        Vec2dNeighbours {
+            vec2d: self,
+            pos,
+            directions: Direction::ALL.iter(),
+        }
+    }
+
+    /// Every position from `pos` in `direction` until the edge, not including `pos` itself.
+    pub fn ray(&self, pos: Position, direction: Direction) -> Vec2dRay<'_, T> {
+        Vec2dRay {
+            vec2d: self,
+            current: Some(pos),
+            direction,
+        }
     }
 
     pub fn transposed(&self) -> Self
     where
         T: Clone,
     {
-        let mut transposed = Vec::with_capacity(self.width());
-        for x in 0..self.width() {
-            let mut row = Vec::with_capacity(self.height());
-            for y in 0..self.height() {
-                row.push(self.data[y][x].clone());
+        let mut transposed = self.clone();
+        transposed.transpose();
+        transposed
+    }
+
+    /// Transposes in place by following the cycles of the permutation.
+    pub fn transpose(&mut self) {
+        let Size { width, height } = self.size;
+        let destination = |i: usize| (i % width) * height + i / width;
+
+        let mut moved = vec![false; self.data.len()];
+        for start in 0..self.data.len() {
+            if moved[start] {
+                continue;
+            }
+            moved[start] = true;
+
+            let mut i = destination(start);
+            while i != start {
+                self.data.swap(start, i);
+                moved[i] = true;
+                i = destination(i);
             }
-            transposed.push(row);
         }
 
-        // Should never fail since the original Vec2d is square
-        Self::new(transposed).unwrap()
+        self.size = Size::new(height, width);
     }
 
-    pub fn modify(&mut self, pos: Position, f: fn(&mut T)) -> Result<(), Vec2dError> {
-        if !self.size.contains(pos) {
-            return Err(Vec2dError::OutOfBounds);
-        }
+    pub fn modify(&mut self, pos: Position, f: impl FnOnce(&mut T)) -> Result<(), Vec2dError> {
+        f(self.get_mut(pos).ok_or(Vec2dError::OutOfBounds)?);
+        Ok(())
+    }
 
-        f(&mut self.data[pos.y][pos.x]);
+    pub fn swap(&mut self, src: Position, dst: Position) -> Result<(), Vec2dError> {
+        let src = self.index_of(src).ok_or(Vec2dError::OutOfBounds)?;
+        let dst = self.index_of(dst).ok_or(Vec2dError::OutOfBounds)?;
+
+        self.data.swap(src, dst);
         Ok(())
     }
+}
 
-    pub fn swap(&mut self, src: Position, dst: Position) -> Result<(), Vec2dError>
-    where
-        T: Copy,
-    {
-        if !self.size.contains(src) || !self.size.contains(dst) {
-            return Err(Vec2dError::OutOfBounds);
+impl<T> Index<Position> for Vec2d<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &Self::Output {
+        match self.index_of(pos) {
+            Some(index) => &self.data[index],
+            None => panic!(
+                "position {pos} is outside {}x{}",
+                self.width(),
+                self.height()
+            ),
         }
+    }
+}
 
-        let tmp = self.data[src.y][src.x];
-        self.data[src.y][src.x] = self.data[dst.y][dst.x];
-        self.data[dst.y][dst.x] = tmp;
-        Ok(())
+impl<T> IndexMut<Position> for Vec2d<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut Self::Output {
+        match self.index_of(pos) {
+            Some(index) => &mut self.data[index],
+            None => panic!(
+                "position {pos} is outside {}x{}",
+                self.width(),
+                self.height()
+            ),
+        }
     }
 }
 
@@ -515,12 +673,45 @@ impl<'a, T> Iterator for Vec2dColIter<'a, T> {
     }
 }
 
+pub struct Vec2dNeighbours<'a, T> {
+    vec2d: &'a Vec2d<T>,
+    pos: Position,
+    directions: slice::Iter<'static, Direction>,
+}
+
+impl<'a, T> Iterator for Vec2dNeighbours<'a, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.directions.find_map(|direction| {
+            let pos = self.pos.step(*direction, &self.vec2d.size)?;
+            Some((pos, &self.vec2d[pos]))
+        })
+    }
+}
+
+pub struct Vec2dRay<'a, T> {
+    vec2d: &'a Vec2d<T>,
+    current: Option<Position>,
+    direction: Direction,
+}
+
+impl<'a, T> Iterator for Vec2dRay<'a, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.current = self.current?.step(self.direction, &self.vec2d.size);
+        let pos = self.current?;
+        Some((pos, &self.vec2d[pos]))
+    }
+}
+
 impl<T> Display for Vec2d<T>
 where
     T: Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        for row in &self.data {
+        for row in self.rows() {
             for item in row {
                 write!(f, "{}", item)?;
             }
@@ -727,6 +918,61 @@ mod tests {
         assert_eq!(vec2d.get(Position::new(3, 2)), Some(&12));
     }
 
+    #[test]
+    fn test_transpose() {
+        for (width, height) in [(1, 1), (1, 5), (5, 1), (3, 3), (4, 3), (3, 7)] {
+            let size = Size::new(width, height);
+            let mut vec2d = Vec2d::from_vec(size, size.iter().collect()).unwrap();
+            vec2d.transpose();
+
+            assert_eq!(vec2d.width(), height);
+            assert_eq!(vec2d.height(), width);
+            for (pos, item) in vec2d.iter() {
+                assert_eq!(item.invert(), pos);
+            }
+        }
+    }
+
+    #[test]
+    fn test_index() {
+        let mut vec2d = Vec2d::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        vec2d[Position::new(2, 0)] = 7;
+        *vec2d.get_mut(Position::new(0, 1)).unwrap() += 10;
+        assert_eq!(vec2d[Position::new(2, 0)], 7);
+        assert_eq!(vec2d.get_mut(Position::new(3, 0)), None);
+
+        assert_eq!(vec2d.row(1), &[14, 5, 6]);
+        assert_eq!(vec2d.col(2).copied().collect::<Vec<_>>(), vec![7, 6]);
+        assert_eq!(
+            vec2d.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 7][..], &[14, 5, 6][..]]
+        );
+
+        assert!(matches!(
+            Vec2d::from_vec(Size::new(2, 2), vec![1, 2, 3]),
+            Err(Vec2dError::SizeMismatch {
+                expected: 4,
+                actual: 3
+            })
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let vec2d = Vec2d::new(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        // Would be the first item of the next row in a flat index
+        let _ = vec2d[Position::new(2, 0)];
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_col_out_of_bounds() {
+        let vec2d = Vec2d::new(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        let _ = vec2d.col(2);
+    }
+
     #[test]
     fn test_modify() {
         let data = vec![vec![1, 2], vec![3, 4]];
@@ -734,11 +980,14 @@ mod tests {
         let mut vec2d = Vec2d::new(data).unwrap();
 
         vec2d.modify(Position::new(0, 0), |x| *x = 5).unwrap();
+        let offset = 10;
+        vec2d.modify(Position::new(1, 1), |x| *x += offset).unwrap();
+        assert!(vec2d.modify(Position::new(2, 1), |x| *x = 0).is_err());
 
         assert_eq!(vec2d.get(Position::new(0, 0)), Some(&5));
         assert_eq!(vec2d.get(Position::new(0, 1)), Some(&3));
         assert_eq!(vec2d.get(Position::new(1, 0)), Some(&2));
-        assert_eq!(vec2d.get(Position::new(1, 1)), Some(&4));
+        assert_eq!(vec2d.get(Position::new(1, 1)), Some(&14));
     }
 
     #[test]
@@ -756,4 +1005,55 @@ mod tests {
         assert_eq!(vec2d.get(Position::new(1, 0)), Some(&2));
         assert_eq!(vec2d.get(Position::new(1, 1)), Some(&1));
     }
+
+    #[test]
+    fn test_step() {
+        let size = Size::new(3, 2);
+        let pos = Position::new(0, 1);
+
+        assert_eq!(pos.step(Direction::North, &size), Some(Position::new(0, 0)));
+        assert_eq!(pos.step(Direction::East, &size), Some(Position::new(1, 1)));
+        assert_eq!(pos.step(Direction::South, &size), None);
+        assert_eq!(pos.step(Direction::West, &size), None);
+        assert_eq!(Position::new(2, 0).step(Direction::NorthEast, &size), None);
+    }
+
+    fn digits() -> Vec2d<char> {
+        Vec2d::new(vec![
+            vec!['1', '2', '3'],
+            vec!['4', '5', '6'],
+            vec!['7', '8', '9'],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let vec2d = digits();
+        let values = |iter: Vec2dNeighbours<char>| iter.map(|(_, v)| *v).collect::<String>();
+
+        assert_eq!(values(vec2d.neighbours4(Position::new(1, 1))), "2684");
+        assert_eq!(values(vec2d.neighbours4(Position::new(0, 0))), "24");
+        assert_eq!(values(vec2d.neighbours8(Position::new(0, 0))), "254");
+        assert_eq!(values(vec2d.neighbours8(Position::new(1, 1))), "23698741");
+        assert_eq!(
+            vec2d.neighbours4(Position::new(2, 2)).next(),
+            Some((Position::new(2, 1), &'6'))
+        );
+    }
+
+    #[test]
+    fn test_ray() {
+        let vec2d = digits();
+
+        let ray = vec2d
+            .ray(Position::new(0, 0), Direction::SouthEast)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ray,
+            vec![(Position::new(1, 1), &'5'), (Position::new(2, 2), &'9')]
+        );
+        assert_eq!(vec2d.ray(Position::new(0, 1), Direction::West).count(), 0);
+        assert_eq!(vec2d.ray(Position::new(0, 1), Direction::East).count(), 2);
+    }
 }
@@ -0,0 +1,303 @@
+use super::{Position, Size, SizeIter, Symmetry, Vec2d};
+use std::ops::Index;
+use strum::IntoEnumIterator;
+
+/// A borrowed rectangle of a [`Vec2d`]. Positions are relative to its top left corner.
+#[derive(Debug)]
+pub struct View<'a, T> {
+    vec2d: &'a Vec2d<T>,
+    origin: Position,
+    size: Size,
+}
+
+// Derived impls would require `T: Clone`
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for View<'_, T> {}
+
+impl<'a, T> View<'a, T> {
+    /// Position of the top left corner in the underlying grid.
+    pub fn origin(&self) -> Position {
+        self.origin
+    }
+
+    pub fn size(&self) -> &Size {
+        &self.size
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&'a T> {
+        self.size
+            .contains(pos)
+            .then(|| &self.vec2d[Position::new(self.origin.x + pos.x, self.origin.y + pos.y)])
+    }
+
+    pub fn row(&self, row: usize) -> &'a [T] {
+        assert!(row < self.size.height, "row {row} is outside the view");
+        &self.vec2d.row(self.origin.y + row)[self.origin.x..self.origin.x + self.size.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + 'a {
+        let view = *self;
+        (0..self.size.height).map(move |row| view.row(row))
+    }
+
+    /// All items with their position in the view, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &'a T)> + 'a {
+        let Self { vec2d, origin, .. } = *self;
+        self.size.iter().map(move |pos| {
+            (
+                pos,
+                &vec2d[Position::new(origin.x + pos.x, origin.y + pos.y)],
+            )
+        })
+    }
+
+    pub fn to_vec2d(&self) -> Vec2d<T>
+    where
+        T: Clone,
+    {
+        Vec2d::new(self.rows().map(|row| row.to_vec()).collect()).expect("a view is never empty")
+    }
+}
+
+impl<T> Index<Position> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "position {pos} is outside {}x{}",
+                self.size.width, self.size.height
+            )
+        })
+    }
+}
+
+impl<T> Vec2d<T> {
+    /// The rectangle of `size` with its top left corner at `origin`, or `None` if it does not
+    /// fit inside the grid or is empty.
+    pub fn view(&self, origin: Position, size: Size) -> Option<View<'_, T>> {
+        let fits = size.area() > 0
+            && origin.x + size.width <= self.width()
+            && origin.y + size.height <= self.height();
+
+        fits.then_some(View {
+            vec2d: self,
+            origin,
+            size,
+        })
+    }
+
+    /// Every view of `size` that fits inside the grid, overlapping, in reading order of their
+    /// top left corners.
+    pub fn windows(&self, size: Size) -> Vec2dWindows<'_, T> {
+        let fits = size.area() > 0 && size.width <= self.width() && size.height <= self.height();
+        let origins = if fits {
+            Size::new(
+                self.width() - size.width + 1,
+                self.height() - size.height + 1,
+            )
+        } else {
+            Size::default()
+        };
+
+        Vec2dWindows {
+            vec2d: self,
+            origins: origins.iter(),
+            size,
+        }
+    }
+}
+
+pub struct Vec2dWindows<'a, T> {
+    vec2d: &'a Vec2d<T>,
+    origins: SizeIter,
+    size: Size,
+}
+
+impl<'a, T> Iterator for Vec2dWindows<'a, T> {
+    type Item = View<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let origin = self.origins.next()?;
+        Some(View {
+            vec2d: self.vec2d,
+            origin,
+            size: self.size,
+        })
+    }
+}
+
+/// A small template to search for in a grid, where `None` cells match anything.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pattern<T> {
+    template: Vec2d<Option<T>>,
+}
+
+impl<T> Pattern<T>
+where
+    T: PartialEq,
+{
+    pub fn new(template: Vec2d<Option<T>>) -> Self {
+        Self { template }
+    }
+
+    /// A pattern where every `wildcard` in `template` matches anything.
+    pub fn with_wildcard(template: &Vec2d<T>, wildcard: &T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(template.map(|item| (item != wildcard).then(|| item.clone())))
+    }
+
+    pub fn size(&self) -> &Size {
+        self.template.size()
+    }
+
+    pub fn matches(&self, view: &View<T>) -> bool {
+        view.size() == self.size()
+            && self
+                .template
+                .iter()
+                .all(|(pos, item)| item.as_ref().is_none_or(|item| *item == view[pos]))
+    }
+
+    /// Top left corners of every placement of the pattern in `vec2d`.
+    pub fn find(&self, vec2d: &Vec2d<T>) -> Vec<Position> {
+        vec2d
+            .windows(*self.size())
+            .filter(|view| self.matches(view))
+            .map(|view| view.origin())
+            .collect()
+    }
+
+    /// The distinct rotations and reflections of the pattern. A symmetric pattern has fewer
+    /// than eight, so that no placement is found twice.
+    pub fn orientations(&self) -> Vec<(Symmetry, Self)>
+    where
+        T: Clone,
+    {
+        let mut orientations: Vec<(Symmetry, Self)> = Vec::new();
+        for symmetry in Symmetry::iter() {
+            let pattern = Self::new(symmetry.apply(&self.template));
+            if orientations.iter().all(|(_, p)| *p != pattern) {
+                orientations.push((symmetry, pattern));
+            }
+        }
+        orientations
+    }
+
+    /// Every placement of any orientation of the pattern, see [`Pattern::orientations`].
+    pub fn find_oriented(&self, vec2d: &Vec2d<T>) -> Vec<(Position, Symmetry)>
+    where
+        T: Clone,
+    {
+        self.orientations()
+            .into_iter()
+            .flat_map(|(symmetry, pattern)| {
+                pattern
+                    .find(vec2d)
+                    .into_iter()
+                    .map(move |pos| (pos, symmetry))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let vec2d = Vec2d::from_rows(&["abcd", "efgh", "ijkl"], |c| c);
+
+        let view = vec2d.view(Position::new(1, 1), Size::new(3, 2)).unwrap();
+        assert_eq!(view.origin(), Position::new(1, 1));
+        assert_eq!(view[Position::new(0, 0)], 'f');
+        assert_eq!(view.get(Position::new(2, 1)), Some(&'l'));
+        assert_eq!(view.get(Position::new(3, 0)), None);
+        assert_eq!(
+            view.rows().collect::<Vec<_>>(),
+            vec![&['f', 'g', 'h'], &['j', 'k', 'l']]
+        );
+        assert_eq!(view.to_vec2d(), Vec2d::from_rows(&["fgh", "jkl"], |c| c));
+        assert_eq!(view.iter().nth(3), Some((Position::new(0, 1), &'j')));
+
+        assert!(vec2d.view(Position::new(2, 0), Size::new(3, 1)).is_none());
+        assert!(vec2d.view(Position::new(0, 0), Size::new(0, 1)).is_none());
+    }
+
+    #[test]
+    fn test_windows() {
+        let vec2d = Vec2d::from_rows(&["abcd", "efgh", "ijkl"], |c| c);
+
+        let windows: Vec<String> = vec2d
+            .windows(Size::new(2, 2))
+            .map(|view| view.iter().map(|(_, c)| *c).collect())
+            .collect();
+        assert_eq!(
+            windows,
+            vec!["abef", "bcfg", "cdgh", "efij", "fgjk", "ghkl"]
+        );
+        assert_eq!(vec2d.windows(Size::new(4, 3)).count(), 1);
+        assert_eq!(vec2d.windows(Size::new(5, 1)).count(), 0);
+    }
+
+    #[test]
+    fn test_pattern() {
+        let vec2d = Vec2d::from_rows(&["#.#.", ".#..", "#.##", "...#"], |c| c);
+        let pattern = Pattern::with_wildcard(&Vec2d::from_rows(&["#?", "?#"], |c| c), &'?');
+
+        assert_eq!(
+            pattern.find(&vec2d),
+            vec![
+                Position::new(0, 0),
+                Position::new(1, 1),
+                Position::new(2, 2)
+            ]
+        );
+        assert_eq!(pattern.orientations().len(), 2);
+
+        let oriented = pattern.find_oriented(&vec2d);
+        assert_eq!(oriented.len(), 5);
+        assert!(oriented.contains(&(Position::new(0, 1), Symmetry::Rotate90)));
+    }
+
+    #[test]
+    fn test_sea_monster() {
+        // 2020 day 20, wildcards in the monster and the sea
+        let monster = Vec2d::from_rows(
+            &[
+                "                  # ",
+                "#    ##    ##    ###",
+                " #  #  #  #  #  #   ",
+            ],
+            |c| c,
+        );
+        let monster = Pattern::with_wildcard(&monster, &' ');
+        assert_eq!(monster.orientations().len(), 8);
+
+        let mut sea = Vec2d::filled(Size::new(24, 5), '.').unwrap();
+        sea[Position::new(0, 0)] = '#';
+        sea[Position::new(23, 4)] = '#';
+        assert!(monster.find(&sea).is_empty());
+
+        for (pos, item) in monster.template.iter() {
+            if item.is_some() {
+                sea[Position::new(pos.x + 2, pos.y + 1)] = '#';
+            }
+        }
+        assert_eq!(monster.find(&sea), vec![Position::new(2, 1)]);
+
+        sea.rotate90();
+        assert_eq!(
+            monster.find_oriented(&sea),
+            vec![(Position::new(1, 2), Symmetry::Rotate90)]
+        );
+    }
+}
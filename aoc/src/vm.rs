@@ -0,0 +1,503 @@
+use crate::AoCError;
+use std::{
+    collections::{HashSet, VecDeque},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// The registers of a [`Machine`], indexed by number. See [`Registers::from_name`] for registers
+/// named by letter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Registers(Vec<i64>);
+
+impl Registers {
+    pub fn new(count: usize) -> Self {
+        Self(vec![0; count])
+    }
+
+    pub fn values(&self) -> &[i64] {
+        &self.0
+    }
+
+    /// The register number of a register named by a lowercase letter, for parsing
+    /// instructions that name their registers.
+    pub fn from_name(name: char) -> Result<usize, AoCError> {
+        if name.is_ascii_lowercase() {
+            Ok((name as u8 - b'a') as usize)
+        } else {
+            Err(AoCError::UnexpectedCharacter(name))
+        }
+    }
+}
+
+impl Index<usize> for Registers {
+    type Output = i64;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl IndexMut<usize> for Registers {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+/// How a [`Machine`] continues after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    /// Jumps relative to the instruction
+    Jump(i64),
+    /// Jumps to an absolute address
+    Goto(i64),
+    /// The instruction cannot run yet, e.g. for lack of input, and is retried next step
+    Wait,
+    Halt,
+}
+
+/// An instruction of a puzzle's instruction set. Instructions get the whole machine so that
+/// they can do I/O or modify the program.
+pub trait Instruction: Clone + Sized {
+    fn execute(&self, machine: &mut Machine<Self>) -> Flow;
+
+    /// Clock cycles the instruction takes.
+    fn cycles(&self) -> u64 {
+        1
+    }
+}
+
+/// Why a [`Machine`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// An instruction halted or the program counter left the program
+    Halted,
+    /// An instruction is waiting, see [`Flow::Wait`]
+    Waiting,
+    /// The next instruction would repeat an earlier point of execution
+    Loop,
+    /// The instruction limit was reached
+    Limit,
+}
+
+/// When a [`Machine`] considers itself to be in an infinite loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopDetection {
+    Off,
+    /// An instruction is about to be executed a second time
+    Pc,
+    /// The program counter and registers are the same as before an earlier instruction and no
+    /// input was received since, see [`Machine::receive`]
+    State,
+}
+
+/// An instruction about to be executed, as passed to trace callbacks.
+#[derive(Debug)]
+pub struct Trace<'a, I> {
+    pub pc: usize,
+    pub instruction: &'a I,
+    pub registers: &'a Registers,
+    /// The clock cycle the instruction starts on, counted from 0
+    pub cycle: u64,
+}
+
+/// A register machine running a list of instructions.
+#[derive(Debug, Clone)]
+pub struct Machine<I> {
+    pub program: Vec<I>,
+    pub registers: Registers,
+    pub pc: i64,
+    pub input: VecDeque<i64>,
+    pub output: Vec<i64>,
+    /// Number of values taken from `input`
+    received: u64,
+    pc_register: Option<usize>,
+    halted: bool,
+    cycle: u64,
+    executed: u64,
+    limit: Option<u64>,
+    loops: LoopDetection,
+    seen: HashSet<(i64, Option<(Registers, u64)>)>,
+}
+
+impl<I: Instruction> Machine<I> {
+    pub fn new(program: Vec<I>, registers: usize) -> Self {
+        Self {
+            program,
+            registers: Registers::new(registers),
+            pc: 0,
+            input: VecDeque::new(),
+            output: Vec::new(),
+            received: 0,
+            pc_register: None,
+            halted: false,
+            cycle: 0,
+            executed: 0,
+            limit: None,
+            loops: LoopDetection::Off,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Parses one instruction per line.
+    pub fn parse(input: &str, registers: usize) -> Result<Self, I::Err>
+    where
+        I: FromStr,
+    {
+        let program = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::new(program, registers))
+    }
+
+    /// Binds the program counter to a register, like `#ip` of 2018 ElfCode: the register is
+    /// set to the program counter before each instruction and read back after it.
+    pub fn bind_pc(&mut self, register: usize) {
+        self.pc_register = Some(register);
+    }
+
+    /// Stops execution after `limit` instructions in total.
+    pub fn limit(&mut self, limit: u64) {
+        self.limit = Some(limit);
+    }
+
+    pub fn detect_loops(&mut self, loops: LoopDetection) {
+        self.loops = loops;
+        self.seen.clear();
+    }
+
+    /// Continues execution at `pc`, also after the machine halted.
+    pub fn goto(&mut self, pc: i64) {
+        self.pc = pc;
+        self.halted = false;
+    }
+
+    /// Takes the next input value for an instruction, which should wait if there is none.
+    pub fn receive(&mut self) -> Option<i64> {
+        let value = self.input.pop_front()?;
+        self.received += 1;
+        Some(value)
+    }
+
+    pub fn halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Clock cycles used so far, see [`Instruction::cycles`].
+    pub fn cycle(&self) -> u64 {
+        self.cycle
+    }
+
+    /// Number of instructions executed so far.
+    pub fn executed(&self) -> u64 {
+        self.executed
+    }
+
+    /// Executes a single instruction, returning why it could not if it did not.
+    pub fn step(&mut self) -> Option<Stop> {
+        self.step_traced(&mut |_| {})
+    }
+
+    pub fn run(&mut self) -> Stop {
+        self.run_traced(|_| {})
+    }
+
+    /// Runs until the machine stops, calling `tracer` before every instruction.
+    pub fn run_traced(&mut self, mut tracer: impl FnMut(&Trace<I>)) -> Stop {
+        loop {
+            if let Some(stop) = self.step_traced(&mut tracer) {
+                return stop;
+            }
+        }
+    }
+
+    fn step_traced(&mut self, tracer: &mut impl FnMut(&Trace<I>)) -> Option<Stop> {
+        let pc = match usize::try_from(self.pc) {
+            Ok(pc) if pc < self.program.len() && !self.halted => pc,
+            _ => {
+                self.halted = true;
+                return Some(Stop::Halted);
+            }
+        };
+        if self.limit.is_some_and(|limit| self.executed >= limit) {
+            return Some(Stop::Limit);
+        }
+
+        let state = match self.loops {
+            LoopDetection::Off => None,
+            LoopDetection::Pc => Some((self.pc, None)),
+            LoopDetection::State => Some((self.pc, Some((self.registers.clone(), self.received)))),
+        };
+        if let Some(state) = &state
+            && self.seen.contains(state)
+        {
+            return Some(Stop::Loop);
+        }
+
+        if let Some(register) = self.pc_register {
+            self.registers[register] = self.pc;
+        }
+
+        let instruction = self.program[pc].clone();
+        tracer(&Trace {
+            pc,
+            instruction: &instruction,
+            registers: &self.registers,
+            cycle: self.cycle,
+        });
+
+        let flow = instruction.execute(self);
+        if flow == Flow::Wait {
+            return Some(Stop::Waiting);
+        }
+        if let Some(state) = state {
+            self.seen.insert(state);
+        }
+        self.executed += 1;
+        self.cycle += instruction.cycles();
+
+        let base = match self.pc_register {
+            Some(register) => self.registers[register],
+            None => self.pc,
+        };
+        let next = match flow {
+            Flow::Next => base.checked_add(1),
+            Flow::Jump(offset) => base.checked_add(offset),
+            Flow::Goto(address) => Some(address),
+            Flow::Wait => unreachable!(),
+            Flow::Halt => None,
+        };
+        match next {
+            Some(pc) => {
+                self.pc = pc;
+                None
+            }
+            // A jump past i64::MAX leaves the program like any other jump out of it
+            None => {
+                self.halted = true;
+                Some(Stop::Halted)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2015 day 23
+    #[derive(Debug, Clone)]
+    enum Lock {
+        Hlf(usize),
+        Tpl(usize),
+        Inc(usize),
+        Jmp(i64),
+        Jie(usize, i64),
+        Jio(usize, i64),
+    }
+
+    impl FromStr for Lock {
+        type Err = AoCError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (op, args) = s.split_once(' ').ok_or(AoCError::BadInput)?;
+            let args: Vec<&str> = args.split(", ").collect();
+            let register =
+                || Registers::from_name(args[0].chars().next().ok_or(AoCError::BadInput)?);
+            let offset = |i: usize| -> Result<i64, AoCError> {
+                Ok(args.get(i).ok_or(AoCError::BadInput)?.parse()?)
+            };
+
+            Ok(match op {
+                "hlf" => Self::Hlf(register()?),
+                "tpl" => Self::Tpl(register()?),
+                "inc" => Self::Inc(register()?),
+                "jmp" => Self::Jmp(offset(0)?),
+                "jie" => Self::Jie(register()?, offset(1)?),
+                "jio" => Self::Jio(register()?, offset(1)?),
+                _ => return Err(AoCError::BadInput),
+            })
+        }
+    }
+
+    impl Instruction for Lock {
+        fn execute(&self, machine: &mut Machine<Self>) -> Flow {
+            let registers = &mut machine.registers;
+            match *self {
+                Self::Hlf(r) => registers[r] /= 2,
+                Self::Tpl(r) => registers[r] *= 3,
+                Self::Inc(r) => registers[r] += 1,
+                Self::Jmp(offset) => return Flow::Jump(offset),
+                Self::Jie(r, offset) if registers[r] % 2 == 0 => return Flow::Jump(offset),
+                Self::Jio(r, offset) if registers[r] == 1 => return Flow::Jump(offset),
+                _ => {}
+            }
+            Flow::Next
+        }
+    }
+
+    #[test]
+    fn examples_y15d23() {
+        let mut machine: Machine<Lock> =
+            Machine::parse("inc a\njio a, +2\ntpl a\ninc a", 2).unwrap();
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.registers[0], 2);
+        assert_eq!(machine.executed(), 3);
+
+        assert!(matches!(
+            "inc A".parse::<Lock>(),
+            Err(AoCError::UnexpectedCharacter('A'))
+        ));
+    }
+
+    /// 2020 day 8
+    #[derive(Debug, Clone)]
+    enum Handheld {
+        Acc(i64),
+        Jmp(i64),
+        Nop,
+    }
+
+    impl Instruction for Handheld {
+        fn execute(&self, machine: &mut Machine<Self>) -> Flow {
+            match *self {
+                Self::Acc(value) => machine.registers[0] += value,
+                Self::Jmp(offset) => return Flow::Jump(offset),
+                Self::Nop => {}
+            }
+            Flow::Next
+        }
+    }
+
+    #[test]
+    fn examples_y20d08() {
+        use Handheld::*;
+        let program = vec![
+            Nop,
+            Acc(1),
+            Jmp(4),
+            Acc(3),
+            Jmp(-3),
+            Acc(-99),
+            Acc(1),
+            Jmp(-4),
+            Acc(6),
+        ];
+
+        let mut machine = Machine::new(program.clone(), 1);
+        machine.detect_loops(LoopDetection::Pc);
+        assert_eq!(machine.run(), Stop::Loop);
+        assert_eq!(machine.registers[0], 5);
+
+        let mut machine = Machine::new(program, 1);
+        machine.limit(10);
+        let mut pcs = Vec::new();
+        assert_eq!(machine.run_traced(|trace| pcs.push(trace.pc)), Stop::Limit);
+        assert_eq!(pcs, vec![0, 1, 2, 6, 7, 3, 4, 1, 2, 6]);
+
+        let mut machine = Machine::new(vec![Acc(1), Jmp(i64::MAX)], 1);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.executed(), 2);
+        assert_eq!(machine.pc, 1);
+    }
+
+    /// 2018 ElfCode, only the instructions of the day 19 example
+    #[derive(Debug, Clone)]
+    enum ElfCode {
+        Seti(i64, usize),
+        Setr(usize, usize),
+        Addi(usize, i64, usize),
+        Addr(usize, usize, usize),
+    }
+
+    impl Instruction for ElfCode {
+        fn execute(&self, machine: &mut Machine<Self>) -> Flow {
+            let registers = &mut machine.registers;
+            match *self {
+                Self::Seti(a, c) => registers[c] = a,
+                Self::Setr(a, c) => registers[c] = registers[a],
+                Self::Addi(a, b, c) => registers[c] = registers[a] + b,
+                Self::Addr(a, b, c) => registers[c] = registers[a] + registers[b],
+            }
+            Flow::Next
+        }
+    }
+
+    #[test]
+    fn examples_y18d19() {
+        use ElfCode::*;
+        let program = vec![
+            Seti(5, 1),
+            Seti(6, 2),
+            Addi(0, 1, 0),
+            Addr(1, 2, 3),
+            Setr(1, 0),
+            Seti(8, 4),
+            Seti(9, 5),
+        ];
+
+        let mut machine = Machine::new(program, 6);
+        machine.bind_pc(0);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.registers.values(), &[6, 5, 6, 0, 0, 9]);
+        assert_eq!(machine.pc, 7);
+
+        // Setting the bound register to the last address steps past it
+        let mut machine = Machine::new(vec![Seti(i64::MAX, 0)], 1);
+        machine.bind_pc(0);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert!(machine.halted());
+    }
+
+    /// Sends every received value on doubled, 2017 day 18 style
+    #[derive(Debug, Clone)]
+    enum Doubler {
+        Rcv(usize),
+        Snd(usize),
+        Jmp(i64),
+    }
+
+    impl Instruction for Doubler {
+        fn execute(&self, machine: &mut Machine<Self>) -> Flow {
+            match *self {
+                Self::Rcv(r) => match machine.receive() {
+                    Some(value) => machine.registers[r] = value,
+                    None => return Flow::Wait,
+                },
+                Self::Snd(r) => machine.output.push(2 * machine.registers[r]),
+                Self::Jmp(offset) => return Flow::Jump(offset),
+            }
+            Flow::Next
+        }
+    }
+
+    #[test]
+    fn test_wait() {
+        use Doubler::*;
+        let mut machine = Machine::new(vec![Rcv(0), Snd(0), Jmp(-2)], 1);
+        machine.detect_loops(LoopDetection::State);
+
+        assert_eq!(machine.run(), Stop::Waiting);
+        machine.input.extend([1, 2]);
+        assert_eq!(machine.run(), Stop::Waiting);
+        assert_eq!(machine.output, vec![2, 4]);
+
+        // Receiving 2 again leads to the same registers as the second value did, but that is
+        // no loop as the machine got new input since
+        machine.input.push_back(2);
+        assert_eq!(machine.run(), Stop::Waiting);
+        assert_eq!(machine.output, vec![2, 4, 4]);
+        assert_eq!(machine.cycle(), 9);
+
+        // Without further input the jump back to itself repeats the state
+        let mut machine = Machine::new(vec![Rcv(0), Snd(0), Jmp(0)], 1);
+        machine.detect_loops(LoopDetection::State);
+        machine.input.push_back(3);
+        assert_eq!(machine.run(), Stop::Loop);
+        assert_eq!(machine.output, vec![6]);
+    }
+}
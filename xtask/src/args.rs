@@ -1,3 +1,5 @@
+use crate::error::*;
+use aoc::problem::Date;
 use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
@@ -5,13 +7,233 @@ use clap::{Parser, Subcommand};
 pub struct Args {
     #[clap(subcommand)]
     pub command: Option<Command>,
-    pub year: u16,
-    pub day: u8,
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+}
+
+impl Args {
+    /// The day given before the command, e.g. `cargo xtask 2025 10 test`.
+    pub fn date(&self) -> Result<(u16, u8)> {
+        let (Some(year), Some(day)) = (self.year, self.day) else {
+            return Err(Error::MissingDay);
+        };
+        Date::new(year, day).map_err(|_| Error::InvalidDay(year, day))?;
+        Ok((year, day))
+    }
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
+    /// Create a new day from the template
     New,
+    /// Add the crate for a new Advent of Code event and create its first day
+    NewYear {
+        year: u16,
+        /// Number of problems in the event, the same as the latest event if omitted
+        #[arg(long)]
+        days: Option<u8>,
+    },
+    /// Run the solution for a day in release mode, the default without a command
     Run,
+    /// Run the tests for a day
     Test,
+    /// Re-run tests and the solution for a day whenever its source or the aoc crate changes
+    Watch,
+    /// Submit the answer of one part of a day and record the verdict
+    Submit {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Submit without asking for confirmation
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Regenerate the README of every year from the sources, answers and runtimes
+    Readme {
+        /// Download missing puzzle pages for the titles
+        #[arg(long)]
+        fetch: bool,
+        /// Run every day in release mode to measure the runtimes
+        #[arg(long)]
+        run: bool,
+        #[command(flatten)]
+        rate: RateArgs,
+    },
+    /// Download puzzle inputs into the cache, replacing any cached copies
+    Fetch {
+        #[command(flatten)]
+        selection: SelectionArgs,
+        #[command(flatten)]
+        rate: RateArgs,
+    },
+    /// Download the inputs of all days with a solution that are not cached yet
+    Prefetch {
+        #[command(flatten)]
+        rate: RateArgs,
+    },
+    /// Inspect and manage the input cache
+    Cache {
+        #[clap(subcommand)]
+        command: CacheCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// List the cached inputs
+    Ls,
+    /// Check the cached inputs for error pages and truncated downloads
+    Verify,
+    /// Remove cached inputs, all of them if no year is given
+    Clear {
+        year: Option<u16>,
+        /// Days such as `3` or ranges such as `1-5`, all days of the year if omitted
+        days: Vec<String>,
+    },
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct SelectionArgs {
+    pub year: u16,
+    /// Days such as `3` or ranges such as `1-5`, all days of the year if omitted
+    pub days: Vec<String>,
+}
+
+impl SelectionArgs {
+    pub fn dates(&self) -> Result<Vec<Date>> {
+        if self.days.is_empty() {
+            let dates: Vec<_> = Date::all(self.year).collect();
+
+            if dates.is_empty() {
+                return Err(Error::InvalidYear(self.year));
+            }
+            return Ok(dates);
+        }
+
+        let mut dates = Vec::new();
+        for days in &self.days {
+            let (first, last) = days.split_once('-').unwrap_or((days, days));
+            let first = first
+                .parse()
+                .map_err(|_| Error::InvalidSelection(days.clone()))?;
+            let last = last
+                .parse()
+                .map_err(|_| Error::InvalidSelection(days.clone()))?;
+            if first > last {
+                return Err(Error::InvalidSelection(days.clone()));
+            }
+
+            for day in first..=last {
+                dates.push(
+                    Date::new(self.year, day).map_err(|_| Error::InvalidDay(self.year, day))?,
+                );
+            }
+        }
+        Ok(dates)
+    }
+}
+
+#[derive(clap::Args, Debug, Clone, Copy)]
+pub struct RateArgs {
+    /// Seconds to wait between downloads, to be polite to the Advent of Code servers
+    #[arg(long, default_value_t = 5)]
+    pub delay: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Args {
+        Args::try_parse_from(["xtask"].into_iter().chain(args.split_whitespace())).unwrap()
+    }
+
+    #[test]
+    fn test_day_commands() {
+        let args = parse("2025 10");
+        assert!(args.command.is_none());
+        assert_eq!(args.date().unwrap(), (2025, 10));
+
+        let args = parse("2025 10 test");
+        assert!(matches!(args.command, Some(Command::Test)));
+        assert_eq!(args.date().unwrap(), (2025, 10));
+
+        let args = parse("2024 5 submit 2 --yes");
+        assert!(matches!(
+            args.command,
+            Some(Command::Submit { part: 2, yes: true })
+        ));
+
+        assert!(matches!(parse("test").date(), Err(Error::MissingDay)));
+        assert!(matches!(
+            parse("2025 13").date(),
+            Err(Error::InvalidDay(2025, 13))
+        ));
+        assert!(Args::try_parse_from(["xtask", "2025", "10", "submit", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_other_commands() {
+        let args = parse("readme --run");
+        assert!(matches!(
+            args.command,
+            Some(Command::Readme {
+                run: true,
+                fetch: false,
+                ..
+            })
+        ));
+        assert_eq!(args.year, None);
+
+        let args = parse("new-year 2026 --days 12");
+        assert!(matches!(
+            args.command,
+            Some(Command::NewYear {
+                year: 2026,
+                days: Some(12)
+            })
+        ));
+    }
+
+    fn dates(year: u16, days: &[&str]) -> Result<Vec<u8>> {
+        let selection = SelectionArgs {
+            year,
+            days: days.iter().map(|days| days.to_string()).collect(),
+        };
+        Ok(selection.dates()?.iter().map(|date| date.day()).collect())
+    }
+
+    #[test]
+    fn test_selection() {
+        assert_eq!(dates(2024, &["3"]).unwrap(), vec![3]);
+        assert_eq!(dates(2024, &["1-4"]).unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            dates(2024, &["7", "1-2", "25-25"]).unwrap(),
+            vec![7, 1, 2, 25]
+        );
+        assert_eq!(dates(2024, &[]).unwrap(), (1..=25).collect::<Vec<_>>());
+        assert_eq!(dates(2025, &[]).unwrap().len(), 12);
+
+        assert!(matches!(dates(2024, &["5-1"]), Err(Error::InvalidSelection(s)) if s == "5-1"));
+        assert!(matches!(
+            dates(2024, &["x"]),
+            Err(Error::InvalidSelection(_))
+        ));
+        assert!(matches!(
+            dates(2024, &["1-"]),
+            Err(Error::InvalidSelection(_))
+        ));
+        assert!(matches!(
+            dates(2024, &["1-2-3"]),
+            Err(Error::InvalidSelection(_))
+        ));
+        assert!(matches!(
+            dates(2025, &["10-13"]),
+            Err(Error::InvalidDay(2025, 13))
+        ));
+        assert!(matches!(
+            dates(2024, &["0"]),
+            Err(Error::InvalidDay(2024, 0))
+        ));
+        assert!(matches!(dates(2014, &[]), Err(Error::InvalidYear(2014))));
+    }
 }
@@ -7,9 +7,30 @@ pub enum Error {
     #[error("Day {1}, {0} does have an Advent of Code problem")]
     InvalidDay(u16, u8),
 
+    #[error("No day given, expected e.g. `cargo xtask 2025 10 test`")]
+    MissingDay,
+
+    #[error("{0} does not have any Advent of Code problems")]
+    InvalidYear(u16),
+
+    #[error("{0} already exists")]
+    YearExists(u16),
+
+    #[error("An event can not have {0} problems")]
+    InvalidDays(u8),
+
+    #[error("Could not find {1} in {0}")]
+    UnexpectedSource(String, &'static str),
+
+    #[error("Invalid day selection {0}, expected a day or a range such as 1-5")]
+    InvalidSelection(String),
+
     #[error("Day {1}, {0} already exists")]
     AlreadyExists(u16, u8),
 
+    #[error("Invalid TOML: {0}")]
+    Toml(#[from] toml_edit::TomlError),
+
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -19,6 +40,33 @@ pub enum Error {
     #[error("Tests failed for day {1}, {0}")]
     TestsFailed(u16, u8),
 
+    #[error("Input cache error: {0}")]
+    Cache(#[from] aoc::cache::CacheError),
+
+    #[error("Answer store error: {0}")]
+    Answers(#[from] aoc::answers::AnswersError),
+
+    #[error("The environment variable SESSION is not set")]
+    NoSession,
+
+    #[error("Failed to fetch {0} input(s)")]
+    FetchFailed(usize),
+
+    #[error("{0} cached input(s) are invalid")]
+    InvalidInputs(usize),
+
+    #[error("Day {1}, {0} has no answer for part {2}")]
+    NoAnswer(u16, u8, u8),
+
+    #[error("Part {2} of day {1}, {0} is already solved with the answer {3}")]
+    AlreadySolved(u16, u8, u8, String),
+
+    #[error("The answer {0} is ruled out, {1} was {2}")]
+    RuledOut(String, String, aoc::answers::Verdict),
+
+    #[error("Submission aborted")]
+    Aborted,
+
     #[error("Error rendering template: {0}")]
     Askama(#[from] askama::Error),
 }
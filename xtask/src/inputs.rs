@@ -0,0 +1,120 @@
+use crate::{args::*, error::*, existing_days};
+use aoc::{
+    cache::{Cache, CacheError},
+    problem::Date,
+};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+pub fn cache_dir(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(".cache")
+}
+
+pub fn online_cache(workspace_root: &Path) -> Result<Cache> {
+    dotenvy::dotenv().ok();
+    let session = env::var("SESSION").map_err(|_| Error::NoSession)?;
+    Ok(Cache::new(cache_dir(workspace_root), session)?)
+}
+
+fn offline_cache(workspace_root: &Path) -> Result<Cache> {
+    Ok(Cache::offline(cache_dir(workspace_root))?)
+}
+
+/// Downloads the inputs one at a time, waiting `rate.delay` seconds between requests.
+/// Failures are reported and the remaining dates are still fetched.
+fn download(cache: &Cache, dates: &[Date], rate: RateArgs) -> Result<()> {
+    let mut failed = 0;
+
+    for (i, date) in dates.iter().enumerate() {
+        if i > 0 {
+            thread::sleep(Duration::from_secs(rate.delay));
+        }
+
+        match cache.fetch(*date) {
+            Ok(input) => println!("Fetched {date} ({} bytes)", input.len()),
+            Err(e) => {
+                eprintln!("Failed to fetch {date}: {e}");
+                failed += 1;
+            }
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        n => Err(Error::FetchFailed(n)),
+    }
+}
+
+pub fn fetch(workspace_root: &Path, selection: &SelectionArgs, rate: RateArgs) -> Result<()> {
+    let dates = selection.dates()?;
+    let cache = online_cache(workspace_root)?;
+
+    download(&cache, &dates, rate)
+}
+
+pub fn prefetch(workspace_root: &Path, rate: RateArgs) -> Result<()> {
+    let cache = online_cache(workspace_root)?;
+    let missing: Vec<_> = existing_days(workspace_root)?
+        .into_iter()
+        .filter(|date| !cache.contains(*date))
+        .collect();
+
+    println!("{} input(s) missing from the cache", missing.len());
+    download(&cache, &missing, rate)
+}
+
+pub fn cache(workspace_root: &Path, command: &CacheCommand) -> Result<()> {
+    let cache = offline_cache(workspace_root)?;
+
+    match command {
+        CacheCommand::Ls => {
+            for date in cache.dates()? {
+                let size = fs::metadata(cache.path(date))?.len();
+                println!("{:<16} {size:>8} bytes", date.to_string());
+            }
+        }
+        CacheCommand::Verify => {
+            let mut invalid = 0;
+            for date in cache.dates()? {
+                match cache.verify(date) {
+                    Ok(()) => {}
+                    Err(CacheError::InvalidInput(reason)) => {
+                        println!("{:<16} {reason}", date.to_string());
+                        invalid += 1;
+                    }
+                    Err(e) => Err(e)?,
+                }
+            }
+
+            if invalid > 0 {
+                Err(Error::InvalidInputs(invalid))?;
+            }
+            println!("All cached inputs look valid");
+        }
+        CacheCommand::Clear { year, days } => {
+            let dates = match year {
+                Some(year) => SelectionArgs {
+                    year: *year,
+                    days: days.clone(),
+                }
+                .dates()?,
+                None => cache.dates()?,
+            };
+
+            let removed = dates
+                .into_iter()
+                .map(|date| cache.remove(date))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            println!(
+                "Removed {} cached input(s)",
+                removed.into_iter().filter(|r| *r).count()
+            );
+        }
+    }
+
+    Ok(())
+}
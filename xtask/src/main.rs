@@ -1,16 +1,24 @@
+use aoc::problem::{Date, OUTPUT_ENV};
 use args::*;
 use askama::Template;
 use clap::Parser;
 use error::*;
 use std::env;
 use std::io::Write;
+use std::process::Stdio;
 use std::{
-    fs::File,
+    fs::{self, File},
     path::{Path, PathBuf},
 };
 
 mod args;
 mod error;
+mod inputs;
+mod new_year;
+mod output;
+mod readme;
+mod submit;
+mod watch;
 
 fn main() -> Result<()> {
     let args = Args::parse();
@@ -18,30 +26,56 @@ fn main() -> Result<()> {
     let xtask_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let workspace_root = xtask_root.ancestors().nth(1).unwrap();
 
-    let (year, day) = match (args.year, args.day) {
-        (y @ 2025, d @ 1..=12) => Ok((y, d)),
-        (y @ 2015..=2024, d @ 1..=25) => Ok((y, d)),
-        (y, d) => Err(Error::InvalidDay(y, d)),
-    }?;
-
     match args.command {
-        None | Some(Command::Run) => run_day(workspace_root, year, day)?,
-        Some(Command::Test) => test_day(workspace_root, year, day)?,
-        Some(Command::New) => new_day(workspace_root, year, day)?,
+        None | Some(Command::Run) => {
+            let (year, day) = args.date()?;
+            run_day(workspace_root, year, day)?
+        }
+        Some(Command::Test) => {
+            let (year, day) = args.date()?;
+            test_day(workspace_root, year, day)?
+        }
+        Some(Command::New) => {
+            let (year, day) = args.date()?;
+            new_day(workspace_root, year, day)?
+        }
+        Some(Command::Watch) => {
+            let (year, day) = args.date()?;
+            watch::watch_day(workspace_root, year, day)?
+        }
+        Some(Command::Submit { part, yes }) => {
+            let (year, day) = args.date()?;
+            submit::submit(workspace_root, year, day, part, yes)?
+        }
+        Some(Command::NewYear { year, days }) => new_year::new_year(workspace_root, year, days)?,
+        Some(Command::Readme { fetch, run, rate }) => {
+            readme::readme(workspace_root, fetch, run, rate)?
+        }
+        Some(Command::Fetch { selection, rate }) => {
+            inputs::fetch(workspace_root, &selection, rate)?
+        }
+        Some(Command::Prefetch { rate }) => inputs::prefetch(workspace_root, rate)?,
+        Some(Command::Cache { command }) => inputs::cache(workspace_root, &command)?,
     }
 
     Ok(())
 }
 
-fn run_day(workspace_root: &Path, year: u16, day: u8) -> Result<()> {
+fn cargo(workspace_root: &Path) -> std::process::Command {
     let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
 
-    let year = year % 100;
-    let binary = format!("y{year:02}d{day:02}");
+    let mut cmd = std::process::Command::new(cargo);
+    cmd.current_dir(workspace_root);
+    cmd
+}
+
+fn binary(year: u16, day: u8) -> String {
+    format!("y{:02}d{day:02}", year % 100)
+}
 
-    let cmd = std::process::Command::new(cargo)
-        .current_dir(workspace_root)
-        .args(["run", "--release", "--bin", &binary])
+fn run_day(workspace_root: &Path, year: u16, day: u8) -> Result<()> {
+    let cmd = cargo(workspace_root)
+        .args(["run", "--release", "--bin", &binary(year, day)])
         .status()?;
 
     if !cmd.success() {
@@ -50,15 +84,26 @@ fn run_day(workspace_root: &Path, year: u16, day: u8) -> Result<()> {
     Ok(())
 }
 
-fn test_day(workspace_root: &Path, year: u16, day: u8) -> Result<()> {
-    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+/// Runs a day in release mode and returns its standard output, compiler messages
+/// are still passed through to the terminal.
+fn solve_day(workspace_root: &Path, year: u16, day: u8, machine: bool) -> Result<String> {
+    let mut cmd = cargo(workspace_root);
+    cmd.args(["run", "-q", "--release", "--bin", &binary(year, day)])
+        .stderr(Stdio::inherit());
+    if machine {
+        cmd.env(OUTPUT_ENV, "machine");
+    }
 
-    let year = year % 100;
-    let binary = format!("y{year:02}d{day:02}");
+    let output = cmd.output()?;
+    if !output.status.success() {
+        Err(Error::RunFailed(year, day))?;
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
 
-    let cmd = std::process::Command::new(cargo)
-        .current_dir(workspace_root)
-        .args(["test", "--bin", &binary])
+fn test_day(workspace_root: &Path, year: u16, day: u8) -> Result<()> {
+    let cmd = cargo(workspace_root)
+        .args(["test", "--bin", &binary(year, day)])
         .status()?;
 
     if !cmd.success() {
@@ -80,24 +125,48 @@ impl DayTemplate {
     }
 }
 
-fn new_day(workspace_root: &Path, year: u16, day: u8) -> Result<()> {
-    match (year, day) {
-        (2025, 1..=12) => Ok(()),
-        (2015..=2024, 1..=25) => Ok(()),
-        (year, day) => Err(Error::InvalidDay(year, day)),
-    }?;
-
-    let target = workspace_root
+fn day_source(workspace_root: &Path, year: u16, day: u8) -> PathBuf {
+    workspace_root
         .join(year.to_string())
         .join("src")
         .join("bin")
-        .join(format!("y{}d{:02}.rs", year - 2000, day));
+        .join(format!("{}.rs", binary(year, day)))
+}
+
+/// All year crates in the workspace, in chronological order.
+fn years(workspace_root: &Path) -> Result<Vec<u16>> {
+    let mut years = Vec::new();
+    for entry in fs::read_dir(workspace_root)? {
+        let entry = entry?;
+        if let Some(year) = entry.file_name().to_str().and_then(|y| y.parse().ok())
+            && entry.path().join("Cargo.toml").exists()
+        {
+            years.push(year);
+        }
+    }
+    years.sort();
+    Ok(years)
+}
+
+/// All days with a solution in the workspace, in chronological order.
+fn existing_days(workspace_root: &Path) -> Result<Vec<Date>> {
+    let mut days = Vec::new();
+    for year in years(workspace_root)? {
+        days.extend(
+            Date::all(year).filter(|date| day_source(workspace_root, year, date.day()).exists()),
+        );
+    }
+    Ok(days)
+}
+
+fn new_day(workspace_root: &Path, year: u16, day: u8) -> Result<()> {
+    let target = day_source(workspace_root, year, day);
     if target.exists() {
         return Err(Error::AlreadyExists(year, day));
     }
 
     let template = DayTemplate::new(year, day);
-    let template = template.render()?;
+    let template = template.render()? + "\n";
     let mut output = File::create_new(target)?;
     output.write_all(template.as_bytes())?;
 
@@ -0,0 +1,230 @@
+use crate::{error::*, new_day};
+use aoc::problem::{EVENTS, days_in_year};
+use askama::Template;
+use std::{fs, path::Path};
+use toml_edit::DocumentMut;
+
+#[derive(Template)]
+#[template(path = "year_cargo.toml", escape = "none")]
+struct YearCargoTemplate {
+    year: u16,
+}
+
+/// Adds the year crate to the workspace, registers the event in `aoc::problem::EVENTS`
+/// unless it is already known and creates day 1 from the template.
+pub fn new_year(workspace_root: &Path, year: u16, days: Option<u8>) -> Result<()> {
+    let crate_dir = workspace_root.join(year.to_string());
+    if crate_dir.exists() {
+        return Err(Error::YearExists(year));
+    }
+    if year < 2015 {
+        return Err(Error::InvalidYear(year));
+    }
+
+    let latest = EVENTS.last().map_or(25, |(_, days)| *days);
+    let days = days.or(days_in_year(year)).unwrap_or(latest);
+    if !(1..=25).contains(&days) {
+        return Err(Error::InvalidDays(days));
+    }
+
+    match days_in_year(year) {
+        Some(known) if known != days => return Err(Error::InvalidDays(days)),
+        Some(_) => {}
+        None => add_event(workspace_root, year, days)?,
+    }
+    add_member(workspace_root, year)?;
+
+    fs::create_dir_all(crate_dir.join("src").join("bin"))?;
+    fs::write(
+        crate_dir.join("Cargo.toml"),
+        YearCargoTemplate { year }.render()? + "\n",
+    )?;
+    new_day(workspace_root, year, 1)?;
+
+    println!("Created {year} with {days} days, run `cargo xtask readme` to add its README");
+    Ok(())
+}
+
+/// Appends the event to `aoc/events.txt`, the source of `aoc::problem::EVENTS`.
+fn add_event(workspace_root: &Path, year: u16, days: u8) -> Result<()> {
+    let path = workspace_root.join("aoc").join("events.txt");
+    let mut events = fs::read_to_string(&path)?;
+
+    if !events.is_empty() && !events.ends_with('\n') {
+        events.push('\n');
+    }
+    events.push_str(&format!("{year} {days}\n"));
+    fs::write(path, events)?;
+    Ok(())
+}
+
+/// Adds the year crate to the workspace members, after the earlier years.
+fn add_member(workspace_root: &Path, year: u16) -> Result<()> {
+    let path = workspace_root.join("Cargo.toml");
+    let mut manifest: DocumentMut = fs::read_to_string(&path)?.parse()?;
+
+    let members = manifest
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("members"))
+        .and_then(|members| members.as_array_mut())
+        .ok_or_else(|| {
+            Error::UnexpectedSource(path.display().to_string(), "the workspace members")
+        })?;
+
+    let name = year.to_string();
+    if members.iter().any(|member| member.as_str() == Some(&name)) {
+        return Err(Error::YearExists(year));
+    }
+
+    // Before the first later year or the first member that is not a year, i.e. xtask
+    let index = members
+        .iter()
+        .position(|member| {
+            member.as_str().is_some_and(|member| {
+                member
+                    .parse::<u16>()
+                    .map_or(member != "aoc", |other| other > year)
+            })
+        })
+        .unwrap_or(members.len());
+
+    // Copy the layout of a neighbouring member so a one member per line list stays that way
+    let decor = members
+        .get(index)
+        .or_else(|| members.get(index.wrapping_sub(1)))
+        .map(|member| member.decor().clone());
+    members.insert(index, name);
+    if let (Some(decor), Some(member)) = (decor, members.get_mut(index)) {
+        *member.decor_mut() = decor;
+    }
+
+    fs::write(path, manifest.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "\
+[workspace]
+members = [
+    \"aoc\",
+    \"2015\",
+    \"2024\",
+    \"xtask\",
+]
+resolver = \"3\"
+";
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("Cargo.toml"), MANIFEST).unwrap();
+        fs::create_dir(dir.path().join("aoc")).unwrap();
+        fs::write(
+            dir.path().join("aoc").join("events.txt"),
+            "# Events\n2015-2024 25",
+        )
+        .unwrap();
+        dir
+    }
+
+    fn members(dir: &Path) -> Vec<String> {
+        let manifest: DocumentMut = fs::read_to_string(dir.join("Cargo.toml"))
+            .unwrap()
+            .parse()
+            .unwrap();
+        manifest["workspace"]["members"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|member| member.as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_add_member() {
+        let dir = workspace();
+
+        add_member(dir.path(), 2030).unwrap();
+        add_member(dir.path(), 2020).unwrap();
+        assert_eq!(
+            members(dir.path()),
+            ["aoc", "2015", "2020", "2024", "2030", "xtask"]
+        );
+        assert!(
+            fs::read_to_string(dir.path().join("Cargo.toml"))
+                .unwrap()
+                .contains("    \"2024\",\n    \"2030\",\n    \"xtask\",\n]\nresolver = \"3\"\n")
+        );
+        assert!(matches!(
+            add_member(dir.path(), 2024),
+            Err(Error::YearExists(2024))
+        ));
+
+        // Still works after the list has been reformatted
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nresolver = \"3\"\nmembers = [\"aoc\", \"2015\", \"xtask\"]\n",
+        )
+        .unwrap();
+        add_member(dir.path(), 2016).unwrap();
+        assert_eq!(members(dir.path()), ["aoc", "2015", "2016", "xtask"]);
+        assert!(
+            fs::read_to_string(dir.path().join("Cargo.toml"))
+                .unwrap()
+                .contains("members = [\"aoc\", \"2015\", \"2016\", \"xtask\"]")
+        );
+
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        assert!(matches!(
+            add_member(dir.path(), 2016),
+            Err(Error::UnexpectedSource(..))
+        ));
+    }
+
+    #[test]
+    fn test_add_event() {
+        let dir = workspace();
+        add_event(dir.path(), 2030, 12).unwrap();
+        add_event(dir.path(), 2031, 10).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("aoc").join("events.txt")).unwrap(),
+            "# Events\n2015-2024 25\n2030 12\n2031 10\n"
+        );
+    }
+
+    #[test]
+    fn test_new_year() {
+        let dir = workspace();
+
+        new_year(dir.path(), 2030, Some(12)).unwrap();
+        assert!(members(dir.path()).contains(&"2030".to_string()));
+        assert!(
+            fs::read_to_string(dir.path().join("aoc").join("events.txt"))
+                .unwrap()
+                .ends_with("\n2030 12\n")
+        );
+        let manifest = fs::read_to_string(dir.path().join("2030").join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"aoc2030\""));
+        assert!(dir.path().join("2030/src/bin/y30d01.rs").exists());
+
+        assert!(matches!(
+            new_year(dir.path(), 2030, None),
+            Err(Error::YearExists(2030))
+        ));
+        assert!(matches!(
+            new_year(dir.path(), 2031, Some(30)),
+            Err(Error::InvalidDays(30))
+        ));
+        assert!(matches!(
+            new_year(dir.path(), 2014, None),
+            Err(Error::InvalidYear(2014))
+        ));
+        // Known events can not change their number of days
+        assert!(matches!(
+            new_year(dir.path(), 2024, Some(12)),
+            Err(Error::InvalidDays(12))
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use std::{fmt::Display, time::Duration};
+
+/// The answers printed by a day binary, with the timing information stripped.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    /// Total time spent solving both parts, only known from machine readable output
+    pub runtime: Option<Duration>,
+}
+
+impl Answers {
+    /// Extracts the answers from the human readable output of both the `AoCProblem`
+    /// and the older `Solution` based binaries.
+    pub fn parse(output: &str) -> Self {
+        let mut answers = Self::default();
+
+        for line in output.lines() {
+            if let Some(answer) = line.strip_prefix("Task 1: ") {
+                answers.part1 = parse_answer(answer);
+            } else if let Some(answer) = line.strip_prefix("Task 2: ") {
+                answers.part2 = parse_answer(answer);
+            }
+        }
+
+        answers
+    }
+
+    /// Extracts the answers from the `part<N> <seconds> <answer>` lines printed when
+    /// the binary runs with machine readable output.
+    pub fn parse_machine(output: &str) -> Self {
+        let mut answers = Self::default();
+
+        for line in output.lines() {
+            let mut fields = line.splitn(3, ' ');
+            let (Some(part), Some(seconds), Some(answer)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+
+            match part {
+                "part1" => answers.part1 = Some(answer.to_string()),
+                "part2" => answers.part2 = Some(answer.to_string()),
+                _ => continue,
+            }
+
+            if let Ok(seconds) = seconds.parse() {
+                let runtime = answers.runtime.unwrap_or_default();
+                answers.runtime = Some(runtime + Duration::from_secs_f64(seconds));
+            }
+        }
+
+        answers
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+fn parse_answer(answer: &str) -> Option<String> {
+    let answer = answer.trim();
+    if answer.is_empty()
+        || answer == "Unsolved"
+        || answer == "Not solved yet"
+        || answer.starts_with("Error: ")
+    {
+        return None;
+    }
+
+    // "<answer> in (<time>)" for AoCProblem and "<answer> (in <time> s)" for Solution
+    let answer = answer
+        .rsplit_once(" in (")
+        .or_else(|| answer.rsplit_once(" (in "))
+        .map_or(answer, |(answer, _)| answer);
+
+    Some(answer.to_string())
+}
+
+/// A part by part comparison of two runs.
+pub struct AnswersDiff<'a> {
+    previous: Option<&'a Answers>,
+    current: &'a Answers,
+}
+
+impl<'a> AnswersDiff<'a> {
+    pub fn new(previous: Option<&'a Answers>, current: &'a Answers) -> Self {
+        Self { previous, current }
+    }
+}
+
+impl Display for AnswersDiff<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in 1..=2 {
+            let current = self.current.get(part).unwrap_or("-");
+            write!(f, "Part {part}: {current}")?;
+
+            match self
+                .previous
+                .map(|previous| previous.get(part).unwrap_or("-"))
+            {
+                None => writeln!(f)?,
+                Some(previous) if previous == current => writeln!(f, " (unchanged)")?,
+                Some(previous) => writeln!(f, " (was {previous})")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        // AoCProblem
+        let answers =
+            Answers::parse("Parsed in (0.0001)\nTask 1: 40 in (0.002)\nTask 2: 25272 in (0.5)\n");
+        assert_eq!(answers.get(1), Some("40"));
+        assert_eq!(answers.get(2), Some("25272"));
+        assert_eq!(answers.runtime, None);
+
+        // Solution
+        let answers =
+            Answers::parse("Parsed (in 0.1 s)\nTask 1: 1234 (in 0.01 s)\nTask 2: Unsolved\n");
+        assert_eq!(answers.get(1), Some("1234"));
+        assert_eq!(answers.get(2), None);
+
+        // Hand written output, answers with spaces and failed parts
+        let answers = Answers::parse("Task 1: 3,1,2 in reverse\nTask 2: Error: Bad input data\n");
+        assert_eq!(answers.get(1), Some("3,1,2 in reverse"));
+        assert_eq!(answers.get(2), None);
+
+        let answers = Answers::parse("Task 1: Not solved yet\nTask 2: RUGE\n");
+        assert_eq!(answers.part1, None);
+        assert_eq!(answers.part2.as_deref(), Some("RUGE"));
+        assert_eq!(answers.get(3), None);
+
+        assert_eq!(Answers::parse("compiling...\n"), Answers::default());
+    }
+
+    #[test]
+    fn test_parse_machine() {
+        let answers = Answers::parse_machine("part1 0.25 40\npart2 0.5 3,1,2 in reverse\n");
+        assert_eq!(answers.get(1), Some("40"));
+        assert_eq!(answers.get(2), Some("3,1,2 in reverse"));
+        assert_eq!(answers.runtime, Some(Duration::from_secs_f64(0.75)));
+
+        // Unsolved parts are not printed, other lines are skipped
+        let answers = Answers::parse_machine("debug output\npart2 0.125 RUGE\npart3 1 x\n");
+        assert_eq!(answers.part1, None);
+        assert_eq!(answers.get(2), Some("RUGE"));
+        assert_eq!(answers.runtime, Some(Duration::from_secs_f64(0.125)));
+
+        // An answer without a valid time is kept, but adds nothing to the runtime
+        let answers = Answers::parse_machine("part1 soon 7\npart2 0.5\n");
+        assert_eq!(answers.get(1), Some("7"));
+        assert_eq!(answers.get(2), None);
+        assert_eq!(answers.runtime, None);
+
+        assert_eq!(Answers::parse_machine(""), Answers::default());
+    }
+}
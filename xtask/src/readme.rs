@@ -0,0 +1,350 @@
+use crate::{
+    args::RateArgs,
+    binary,
+    error::*,
+    existing_days,
+    inputs::{cache_dir, online_cache},
+    output::Answers,
+    solve_day, years,
+};
+use aoc::{answers::AnswerStore, cache::Cache, problem::Date};
+use askama::Template;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+#[derive(Template)]
+#[template(path = "year_readme.md", escape = "none")]
+struct YearReadme {
+    year: u16,
+    days: Vec<DayRow>,
+    has_stars: bool,
+    has_runtimes: bool,
+}
+
+struct DayRow {
+    day: u8,
+    title: String,
+    url: String,
+    stars: String,
+    runtime: String,
+    source: String,
+}
+
+/// Regenerates the per-year READMEs and the year list in the root README.
+pub fn readme(workspace_root: &Path, fetch: bool, run: bool, rate: RateArgs) -> Result<()> {
+    let cache = if fetch {
+        online_cache(workspace_root)?
+    } else {
+        Cache::offline(cache_dir(workspace_root))?
+    };
+    let store = AnswerStore::open(cache_dir(workspace_root))?;
+    let mut runtimes = Runtimes::load(workspace_root)?;
+
+    if fetch {
+        fetch_puzzles(workspace_root, &cache, rate)?;
+    }
+    if run {
+        runtimes.measure(workspace_root)?;
+    }
+
+    let years = years(workspace_root)?;
+    for year in &years {
+        let path = workspace_root.join(year.to_string()).join("README.md");
+        let old_titles = fs::read_to_string(&path)
+            .map(|readme| titles_from_readme(&readme))
+            .unwrap_or_default();
+
+        let mut days = Vec::new();
+        for date in Date::all(*year) {
+            let day = date.day();
+            let title = match cache.cached_puzzle(date)? {
+                Some(page) => title_from_puzzle(&page),
+                None => None,
+            };
+            let source = Path::new("src")
+                .join("bin")
+                .join(format!("{}.rs", binary(*year, day)));
+            let source = if workspace_root.join(year.to_string()).join(&source).exists() {
+                format!("[{}]({})", binary(*year, day), source.display())
+            } else {
+                String::new()
+            };
+
+            days.push(DayRow {
+                day,
+                title: title
+                    .or_else(|| old_titles.get(&day).cloned())
+                    .unwrap_or_else(|| format!("Day {day}")),
+                url: date.puzzle_url(),
+                stars: "⭐".repeat(store.stars(date)),
+                runtime: runtimes.get(date).map(format_duration).unwrap_or_default(),
+                source,
+            });
+        }
+
+        let readme = YearReadme {
+            year: *year,
+            has_stars: days.iter().any(|row| !row.stars.is_empty()),
+            has_runtimes: days.iter().any(|row| !row.runtime.is_empty()),
+            days,
+        };
+        fs::write(&path, readme.render()?)?;
+        println!("Generated {}", path.display());
+    }
+
+    update_root_readme(workspace_root, &years)
+}
+
+fn fetch_puzzles(workspace_root: &Path, cache: &Cache, rate: RateArgs) -> Result<()> {
+    let missing = existing_days(workspace_root)?
+        .into_iter()
+        .filter(|date| cache.cached_puzzle(*date).is_ok_and(|page| page.is_none()));
+
+    for (i, date) in missing.enumerate() {
+        if i > 0 {
+            thread::sleep(Duration::from_secs(rate.delay));
+        }
+
+        match cache.puzzle(date) {
+            Ok(_) => println!("Fetched the puzzle page for {date}"),
+            Err(e) => eprintln!("Failed to fetch the puzzle page for {date}: {e}"),
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the list following `**Year:**` in the root README with all years in the workspace.
+fn update_root_readme(workspace_root: &Path, years: &[u16]) -> Result<()> {
+    let path = workspace_root.join("README.md");
+    let readme = fs::read_to_string(&path)?;
+
+    let mut lines = Vec::new();
+    let mut in_list = false;
+    for line in readme.lines() {
+        if line == "**Year:**" {
+            lines.push(line.to_string());
+            lines.extend(
+                years
+                    .iter()
+                    .map(|year| format!(" - [{year}]({year}/README.md)")),
+            );
+            in_list = true;
+        } else if !(in_list && line.starts_with(" - ")) {
+            lines.push(line.to_string());
+            in_list = false;
+        }
+    }
+
+    fs::write(&path, lines.join("\n") + "\n")?;
+    println!("Generated {}", path.display());
+    Ok(())
+}
+
+/// Extracts the title from the `<h2>--- Day 1: Title ---</h2>` heading of a puzzle page.
+fn title_from_puzzle(page: &str) -> Option<String> {
+    let (_, heading) = page.split_once("<h2>--- Day ")?;
+    let (heading, _) = heading.split_once(" ---</h2>")?;
+    let (_, title) = heading.split_once(": ")?;
+
+    Some(
+        title
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&"),
+    )
+}
+
+/// Titles from a previously generated README, or the older `- [Day 1](...) - Title` lists,
+/// so that titles survive without cached puzzle pages.
+fn titles_from_readme(readme: &str) -> HashMap<u8, String> {
+    let mut titles = HashMap::new();
+
+    for line in readme.lines() {
+        if let Some(row) = line.strip_prefix("| ") {
+            // | 1 | [Title](url) | ...
+            let mut cells = row.split(" | ");
+            if let (Some(day), Some(title)) = (cells.next(), cells.next())
+                && let Ok(day) = day.parse()
+                && let Some(title) = link_text(title)
+                && title != format!("Day {day}")
+            {
+                titles.insert(day, title.to_string());
+            }
+        } else if let Some(item) = line.strip_prefix("- [Day ") {
+            // - [Day 1](src/bin/y15d01.rs) - Title, where the title may be a link
+            if let Some((day, rest)) = item.split_once(']')
+                && let Ok(day) = day.parse()
+                && let Some((_, title)) = rest.split_once(" - ")
+            {
+                let title = link_text(title).unwrap_or(title).trim();
+                if !title.is_empty() {
+                    titles.insert(day, title.to_string());
+                }
+            }
+        }
+    }
+
+    titles
+}
+
+fn link_text(link: &str) -> Option<&str> {
+    let (text, _) = link.strip_prefix('[')?.split_once("](")?;
+    Some(text)
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+    if seconds < 1e-3 {
+        format!("{:.0} µs", seconds * 1e6)
+    } else if seconds < 1.0 {
+        format!("{:.1} ms", seconds * 1e3)
+    } else {
+        format!("{seconds:.2} s")
+    }
+}
+
+/// The total runtime of each day from the latest `readme --run`, kept next to the inputs.
+struct Runtimes {
+    path: PathBuf,
+    runtimes: HashMap<Date, Duration>,
+}
+
+impl Runtimes {
+    fn load(workspace_root: &Path) -> Result<Self> {
+        let path = cache_dir(workspace_root).join("runtimes.tsv");
+
+        let mut runtimes = HashMap::new();
+        if path.exists() {
+            for line in fs::read_to_string(&path)?.lines() {
+                let mut fields = line.split('\t');
+                if let (Some(year), Some(day), Some(seconds)) =
+                    (fields.next(), fields.next(), fields.next())
+                    && let (Ok(year), Ok(day), Ok(seconds)) =
+                        (year.parse(), day.parse(), seconds.parse())
+                    && let Ok(date) = Date::new(year, day)
+                {
+                    runtimes.insert(date, Duration::from_secs_f64(seconds));
+                }
+            }
+        }
+
+        Ok(Self { path, runtimes })
+    }
+
+    fn get(&self, date: Date) -> Option<Duration> {
+        self.runtimes.get(&date).copied()
+    }
+
+    /// Runs every existing day in release mode, failing days keep their previous runtime.
+    fn measure(&mut self, workspace_root: &Path) -> Result<()> {
+        for date in existing_days(workspace_root)? {
+            println!("Running {date}");
+            match solve_day(workspace_root, date.year(), date.day(), true) {
+                Ok(output) => {
+                    if let Some(runtime) = Answers::parse_machine(&output).runtime {
+                        self.runtimes.insert(date, runtime);
+                    }
+                }
+                Err(e) => eprintln!("{e}"),
+            }
+        }
+
+        let mut dates: Vec<_> = self.runtimes.keys().copied().collect();
+        dates.sort();
+        let lines: String = dates
+            .into_iter()
+            .map(|date| {
+                format!(
+                    "{}\t{}\t{}\n",
+                    date.year(),
+                    date.day(),
+                    self.runtimes[&date].as_secs_f64()
+                )
+            })
+            .collect();
+        fs::write(&self.path, lines)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_from_puzzle() {
+        let page = "<main>\n<article class=\"day-desc\"><h2>--- Day 15: Warehouse Woes ---</h2><p>";
+        assert_eq!(title_from_puzzle(page).as_deref(), Some("Warehouse Woes"));
+
+        let page = "<h2>--- Day 7: Some &quot;Assembly&quot; &amp; Joe&#39;s &lt;tags&gt; ---</h2>";
+        assert_eq!(
+            title_from_puzzle(page).as_deref(),
+            Some("Some \"Assembly\" & Joe's <tags>")
+        );
+
+        assert_eq!(title_from_puzzle("<h2>--- Part Two ---</h2>"), None);
+        assert_eq!(title_from_puzzle("<h2>--- Day 1: Unterminated"), None);
+    }
+
+    #[test]
+    fn test_titles_from_readme() {
+        let table = "\
+| Day | Puzzle | Source |
+| --: | ------ | ------ |
+| 1 | [Historian Hysteria](https://adventofcode.com/2024/day/1) | [y24d01](src/bin/y24d01.rs) |
+| 2 | [Day 2](https://adventofcode.com/2024/day/2) |  |
+| 10 | [Hoof It](https://adventofcode.com/2024/day/10) | ⭐⭐ | 1.2 ms | [y24d10](src/bin/y24d10.rs) |
+";
+        let titles = titles_from_readme(table);
+        assert_eq!(titles.len(), 2);
+        assert_eq!(titles[&1], "Historian Hysteria");
+        assert_eq!(titles[&10], "Hoof It");
+
+        let list = "\
+- [Day 1](src/bin/y24d01.rs) - Historian Hysteria
+- [Day 2](src/bin/y24d02.rs) - [Red-Nosed Reports](https://adventofcode.com/2024/day/2)
+- [Day 15](src/bin/y24d15.rs) - 
+- Day 16
+";
+        let titles = titles_from_readme(list);
+        assert_eq!(titles.len(), 2);
+        assert_eq!(titles[&1], "Historian Hysteria");
+        assert_eq!(titles[&2], "Red-Nosed Reports");
+
+        assert_eq!(link_text("[Hoof It](url)"), Some("Hoof It"));
+        assert_eq!(link_text("Hoof It"), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(42)), "42 µs");
+        assert_eq!(format_duration(Duration::from_micros(1250)), "1.2 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50 s");
+    }
+
+    #[test]
+    fn test_update_root_readme() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("README.md");
+        fs::write(
+            &path,
+            "# AoC\n\n**Year:**\n - [2015](2015/README.md)\n\n## Setup\n - keep this\n",
+        )
+        .unwrap();
+
+        update_root_readme(dir.path(), &[2015, 2016]).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# AoC\n\n**Year:**\n - [2015](2015/README.md)\n - [2016](2016/README.md)\n\n\
+             ## Setup\n - keep this\n"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    error::*,
+    inputs::{cache_dir, online_cache},
+    output::Answers,
+    solve_day,
+};
+use aoc::{
+    answers::{AnswerStore, Record, Submission},
+    problem::Date,
+};
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+/// Runs the day in release mode and submits the answer of `part`, unless the answer
+/// store already knows the part is solved or the answer is wrong.
+pub fn submit(workspace_root: &Path, year: u16, day: u8, part: u8, yes: bool) -> Result<()> {
+    let date = Date::new(year, day).map_err(|_| Error::InvalidDay(year, day))?;
+    let mut store = AnswerStore::open(cache_dir(workspace_root))?;
+
+    if let Some(correct) = store.correct(date, part) {
+        return Err(Error::AlreadySolved(year, day, part, correct.to_string()));
+    }
+
+    let output = solve_day(workspace_root, year, day, true)?;
+    let answer = Answers::parse_machine(&output)
+        .get(part)
+        .map(str::to_string)
+        .ok_or(Error::NoAnswer(year, day, part))?;
+
+    if let Some(record) = store.ruled_out_by(date, part, &answer) {
+        return Err(Error::RuledOut(
+            answer,
+            record.answer.clone(),
+            record.verdict,
+        ));
+    }
+
+    if !yes && !confirm(&format!("Submit {answer} for {date} part {part}?"))? {
+        return Err(Error::Aborted);
+    }
+
+    let cache = online_cache(workspace_root)?;
+    match cache.submit(date, part, &answer)? {
+        Submission::Verdict(verdict) => {
+            println!("{answer} is {verdict}");
+            store.record(Record {
+                date,
+                part,
+                verdict,
+                answer,
+            })?;
+        }
+        Submission::TooRecent(Some(wait)) => println!("Answer submitted too recently, wait {wait}"),
+        Submission::TooRecent(None) => println!("Answer submitted too recently"),
+        Submission::AlreadySolved => println!("Part {part} of {date} is already solved"),
+        Submission::Unknown(response) => println!("Unexpected response:\n{response}"),
+    }
+
+    Ok(())
+}
+
+fn confirm(question: &str) -> Result<bool> {
+    print!("{question} [y/N] ");
+    io::stdout().flush()?;
+
+    let mut reply = String::new();
+    io::stdin().read_line(&mut reply)?;
+    Ok(matches!(reply.trim(), "y" | "Y" | "yes"))
+}
@@ -0,0 +1,122 @@
+use crate::{
+    day_source,
+    error::*,
+    output::{Answers, AnswersDiff},
+    solve_day, test_day,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type Snapshot = Vec<(PathBuf, SystemTime)>;
+
+/// Polls the day source and the `aoc` crate, re-running the tests and the solution
+/// on every change. Failures are reported and watching continues.
+pub fn watch_day(workspace_root: &Path, year: u16, day: u8) -> Result<()> {
+    let watched = [
+        day_source(workspace_root, year, day),
+        workspace_root.join(year.to_string()).join("Cargo.toml"),
+        workspace_root.join("aoc").join("src"),
+        workspace_root.join("aoc").join("Cargo.toml"),
+    ];
+
+    let mut previous_snapshot = None;
+    let mut previous_answers = None;
+
+    loop {
+        let snapshot = match snapshot(&watched) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                // E.g. a file replaced by an editor while listing its directory, try again
+                eprintln!("{e}");
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+        };
+        if previous_snapshot.as_ref() != Some(&snapshot) {
+            previous_snapshot = Some(snapshot);
+
+            match test_and_solve(workspace_root, year, day) {
+                Ok(answers) => {
+                    print!("{}", AnswersDiff::new(previous_answers.as_ref(), &answers));
+                    previous_answers = Some(answers);
+                }
+                Err(e) => eprintln!("{e}"),
+            }
+            println!("Watching {year} day {day} for changes...");
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn test_and_solve(workspace_root: &Path, year: u16, day: u8) -> Result<Answers> {
+    test_day(workspace_root, year, day)?;
+    let output = solve_day(workspace_root, year, day, false)?;
+    print!("{output}");
+    Ok(Answers::parse(&output))
+}
+
+fn snapshot(paths: &[PathBuf]) -> Result<Snapshot> {
+    let mut snapshot = Vec::new();
+    for path in paths {
+        collect_modified(path, &mut snapshot)?;
+    }
+    snapshot.sort();
+    Ok(snapshot)
+}
+
+fn collect_modified(path: &Path, snapshot: &mut Snapshot) -> Result<()> {
+    let Ok(metadata) = fs::metadata(path) else {
+        // Missing files are simply not part of the snapshot, e.g. a day not created yet
+        return Ok(());
+    };
+
+    if metadata.is_dir() {
+        let entries = match fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        // Entries that vanish while listing are skipped like missing files
+        for entry in entries.flatten() {
+            collect_modified(&entry.path(), snapshot)?;
+        }
+    } else {
+        snapshot.push((path.to_path_buf(), metadata.modified()?));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("utils")).unwrap();
+        fs::write(src.join("lib.rs"), "").unwrap();
+        fs::write(src.join("utils").join("mod.rs"), "").unwrap();
+
+        let watched = [src.clone(), dir.path().join("missing.rs")];
+        let first = snapshot(&watched).unwrap();
+        let files: Vec<_> = first.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(
+            files,
+            vec![src.join("lib.rs"), src.join("utils").join("mod.rs")]
+        );
+
+        // A removed file drops out of the snapshot instead of failing it
+        fs::remove_file(src.join("lib.rs")).unwrap();
+        let second = snapshot(&watched).unwrap();
+        assert_eq!(second.len(), 1);
+        assert_ne!(first, second);
+    }
+}